@@ -31,7 +31,10 @@ impl TryFrom<&DataChunk> for arrow_array::RecordBatch {
     type Error = ArrayError;
 
     fn try_from(chunk: &DataChunk) -> Result<Self, Self::Error> {
-        let columns: Vec<_> = chunk
+        // Arrow has no notion of a visibility bitmap, so hidden rows must be physically
+        // removed before the columns are handed over.
+        let compacted = chunk.clone().compact();
+        let columns: Vec<_> = compacted
             .columns()
             .iter()
             .map(|column| column.as_ref().try_into())
@@ -755,6 +758,37 @@ mod tests {
         assert_eq!(IntervalArray::from(&arrow), array);
     }
 
+    #[test]
+    fn data_chunk_roundtrip() {
+        let chunk = DataChunk::new(
+            vec![
+                I32Array::from_iter([Some(1), Some(2), Some(3)]).into_ref(),
+                Utf8Array::from_iter([Some("a"), None, Some("c")]).into_ref(),
+            ],
+            3,
+        );
+        let batch = arrow_array::RecordBatch::try_from(&chunk).unwrap();
+        assert_eq!(DataChunk::try_from(&batch).unwrap(), chunk);
+    }
+
+    #[test]
+    fn data_chunk_roundtrip_with_invisible_rows() {
+        let chunk = DataChunk::new(
+            vec![
+                I32Array::from_iter([Some(1), Some(2), Some(3)]).into_ref(),
+                Utf8Array::from_iter([Some("a"), Some("b"), Some("c")]).into_ref(),
+            ],
+            3,
+        )
+        .with_visibility(Bitmap::from_iter([true, false, true]));
+
+        // Converting to Arrow has no notion of a visibility bitmap, so the hidden row must be
+        // compacted away rather than leaking into the `RecordBatch`.
+        let batch = arrow_array::RecordBatch::try_from(&chunk).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(DataChunk::try_from(&batch).unwrap(), chunk.compact());
+    }
+
     #[test]
     fn string() {
         let array = Utf8Array::from_iter([None, Some("array"), Some("arrow")]);
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::hash::BuildHasher;
 use std::{fmt, usize};
 
@@ -19,7 +21,7 @@ use bytes::Bytes;
 use itertools::Itertools;
 use risingwave_pb::data::PbDataChunk;
 
-use super::{Array, ArrayImpl, ArrayRef, ArrayResult, StructArray, Vis};
+use super::{Array, ArrayError, ArrayImpl, ArrayRef, ArrayResult, StructArray, Vis};
 use crate::array::data_chunk_iter::RowRef;
 use crate::array::ArrayBuilderImpl;
 use crate::buffer::{Bitmap, BitmapBuilder};
@@ -27,9 +29,9 @@ use crate::estimate_size::EstimateSize;
 use crate::field_generator::{FieldGeneratorImpl, VarcharProperty};
 use crate::hash::HashCode;
 use crate::row::Row;
-use crate::types::{DataType, DatumRef, StructType, ToOwnedDatum, ToText};
-use crate::util::hash_util::finalize_hashers;
+use crate::types::{DataType, Datum, DatumRef, StructType, ToDatumRef, ToOwnedDatum, ToText};
 use crate::util::iter_util::{ZipEqDebug, ZipEqFast};
+use crate::util::sort_util::{cmp_datum, OrderType};
 use crate::util::value_encoding::{
     estimate_serialize_datum_size, serialize_datum_into, try_get_exact_serialize_datum_size,
     ValueRowSerializer,
@@ -65,13 +67,35 @@ impl DataChunk {
     /// Create a `DataChunk` with `columns` and visibility. The visibility can either be a `Bitmap`
     /// or a simple cardinality number.
     pub fn new<V: Into<Vis>>(columns: Vec<ArrayRef>, vis: V) -> Self {
-        let vis: Vis = vis.into();
-        let capacity = vis.len();
-        for column in &columns {
-            assert_eq!(capacity, column.len());
+        let chunk = DataChunk {
+            columns,
+            vis2: vis.into(),
+        };
+        if cfg!(debug_assertions) {
+            chunk.assert_valid();
         }
+        chunk
+    }
 
-        DataChunk { columns, vis2: vis }
+    /// Asserts that this chunk's columns and visibility are internally consistent: every column
+    /// has the same length, and that length matches the visibility's length (i.e. [`Self::capacity`]).
+    /// In debug builds, [`Self::new`] checks this on every construction, so [`Self::compact`],
+    /// [`Self::rechunk`], and [`Self::from_protobuf`] inherit the check by building through it.
+    ///
+    /// # Panics
+    /// Panics naming the offending column and its length if any invariant is violated.
+    pub fn assert_valid(&self) {
+        let capacity = self.vis2.len();
+        for (i, column) in self.columns.iter().enumerate() {
+            assert_eq!(
+                column.len(),
+                capacity,
+                "DataChunk column {} has length {} but visibility has length {}",
+                i,
+                column.len(),
+                capacity,
+            );
+        }
     }
 
     /// `new_dummy` creates a data chunk without columns but only a cardinality.
@@ -102,6 +126,45 @@ impl DataChunk {
         DataChunk::new(new_columns, rows.len())
     }
 
+    /// Like [`Self::from_rows`], but returns an error instead of panicking when a row's width
+    /// doesn't match `data_types`, or when a value's type doesn't match its column's declared
+    /// type. Useful for building a chunk from already-in-memory `Datum` rows whose shape hasn't
+    /// been validated yet, e.g. in test fixtures or the DML `RETURNING` path.
+    pub fn try_from_rows(rows: &[Vec<Datum>], data_types: &[DataType]) -> ArrayResult<Self> {
+        let mut array_builders = data_types
+            .iter()
+            .map(|data_type| data_type.create_array_builder(rows.len()))
+            .collect::<Vec<_>>();
+
+        for row in rows {
+            if row.len() != data_types.len() {
+                return Err(ArrayError::internal(format!(
+                    "row has {} columns but schema has {}",
+                    row.len(),
+                    data_types.len()
+                )));
+            }
+            for (datum, builder) in row.iter().zip_eq_fast(array_builders.iter_mut()) {
+                if let Some(scalar_ref) = datum.to_datum_ref() {
+                    if builder.get_ident() != scalar_ref.get_ident() {
+                        return Err(ArrayError::internal(format!(
+                            "value of type {} does not match column type {}",
+                            scalar_ref.get_ident(),
+                            builder.get_ident(),
+                        )));
+                    }
+                }
+                builder.append(datum);
+            }
+        }
+
+        let new_columns = array_builders
+            .into_iter()
+            .map(|builder| builder.finish().into())
+            .collect::<Vec<_>>();
+        Ok(DataChunk::new(new_columns, rows.len()))
+    }
+
     /// Return the next visible row index on or after `row_idx`.
     pub fn next_visible_row_idx(&self, row_idx: usize) -> Option<usize> {
         match &self.vis2 {
@@ -116,6 +179,16 @@ impl DataChunk {
         }
     }
 
+    /// Returns the physical row indices whose visibility bit is set, in ascending order. When
+    /// this chunk has no visibility mask (i.e. every row is visible), this is simply `0..cardinality`,
+    /// without materializing a bitmap just to walk it.
+    pub fn visible_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        match &self.vis2 {
+            Vis::Bitmap(vis) => itertools::Either::Left(vis.iter_ones()),
+            Vis::Compact(cardinality) => itertools::Either::Right(0..*cardinality),
+        }
+    }
+
     pub fn into_parts(self) -> (Vec<ArrayRef>, Vis) {
         (self.columns, self.vis2)
     }
@@ -217,6 +290,25 @@ impl DataChunk {
         proto
     }
 
+    /// Like [`Self::to_protobuf`], but serializes only the columns at `indices`, in the given
+    /// order. Meant for an exchange that only needs a subset of columns downstream, so it doesn't
+    /// have to pay the bandwidth cost of the full chunk.
+    ///
+    /// Panics if not compacted, for the same reason as [`Self::to_protobuf`].
+    pub fn to_protobuf_projected(&self, indices: &[usize]) -> PbDataChunk {
+        assert!(
+            matches!(self.vis2, Vis::Compact(_)),
+            "must be compacted before transfer"
+        );
+        PbDataChunk {
+            cardinality: self.cardinality() as u32,
+            columns: indices
+                .iter()
+                .map(|&i| self.columns[i].to_protobuf())
+                .collect(),
+        }
+    }
+
     /// `compact` will convert the chunk to compact format.
     /// Compacting removes the hidden rows, and returns a new visibility
     /// mask which indicates this.
@@ -234,6 +326,13 @@ impl DataChunk {
         match &self.vis2 {
             Vis::Compact(_) => self,
             Vis::Bitmap(visibility) => {
+                if visibility.all() {
+                    // Every row is visible, so there is nothing to compact away. Drop the
+                    // bitmap without touching the columns, to avoid copying every column just to
+                    // produce an identical result (common after a filter that kept everything).
+                    let cardinality = visibility.len();
+                    return Self::new(self.columns, cardinality);
+                }
                 let cardinality = visibility.count_ones();
                 let columns = self
                     .columns
@@ -249,13 +348,24 @@ impl DataChunk {
     }
 
     pub fn from_protobuf(proto: &PbDataChunk) -> ArrayResult<Self> {
+        let cardinality = proto.get_cardinality() as usize;
         let mut columns = vec![];
-        for any_col in proto.get_columns() {
-            let cardinality = proto.get_cardinality() as usize;
-            columns.push(ArrayImpl::from_protobuf(any_col, cardinality)?.into());
+        for (idx, any_col) in proto.get_columns().iter().enumerate() {
+            let array = ArrayImpl::from_protobuf(any_col, cardinality)?;
+            // Most array encodings trust `cardinality` to read exactly that many values, but a
+            // few (e.g. struct/list) derive their own length from the wire data, so a corrupted
+            // message could otherwise silently produce a column whose length disagrees with the
+            // chunk's stated cardinality.
+            if array.len() != cardinality {
+                return Err(ArrayError::internal(format!(
+                    "DataChunk column {idx} has length {} but stated cardinality is {cardinality}",
+                    array.len()
+                )));
+            }
+            columns.push(array.into());
         }
 
-        let chunk = DataChunk::new(columns, proto.cardinality as usize);
+        let chunk = DataChunk::new(columns, cardinality);
         Ok(chunk)
     }
 
@@ -341,6 +451,136 @@ impl DataChunk {
         Ok(new_chunks)
     }
 
+    /// Like [`Self::rechunk`], but the rows of the returned chunks are in the reverse of the
+    /// concatenated input order. Useful for backward-scan operators that want rechunked output
+    /// without a separate sort pass. Chunk-size boundaries are the same as [`Self::rechunk`]
+    /// would produce; only the chunk order and the row order within each chunk are reversed,
+    /// which is equivalent to reversing the whole concatenated row sequence.
+    pub fn rechunk_reversed(chunks: &[DataChunk], each_size_limit: usize) -> ArrayResult<Vec<DataChunk>> {
+        let mut new_chunks = Self::rechunk(chunks, each_size_limit)?;
+        new_chunks.reverse();
+        for chunk in &mut new_chunks {
+            let reversed_indexes = (0..chunk.capacity()).rev().collect_vec();
+            *chunk = chunk.reorder_rows(&reversed_indexes);
+        }
+        Ok(new_chunks)
+    }
+
+    /// Merges several chunks that are each already sorted by `key_col` into a single chunk whose
+    /// rows are globally sorted by that column, using a k-way heap merge. Nulls are ordered last
+    /// regardless of `ascending`. Invisible rows are skipped. Errors if `chunks` is empty, if
+    /// `key_col` is out of bounds, or if the chunks don't share the same schema.
+    pub fn merge_sorted(chunks: &[DataChunk], key_col: usize, ascending: bool) -> ArrayResult<DataChunk> {
+        if chunks.is_empty() {
+            return Err(ArrayError::internal("merge_sorted: `chunks` must not be empty"));
+        }
+        let data_types = chunks[0].data_types();
+        if key_col >= data_types.len() {
+            return Err(ArrayError::internal(format!(
+                "merge_sorted: key_col {} out of bounds for chunk with {} columns",
+                key_col,
+                data_types.len()
+            )));
+        }
+        for chunk in chunks {
+            if chunk.data_types() != data_types {
+                return Err(ArrayError::internal(
+                    "merge_sorted: all chunks must have the same schema",
+                ));
+            }
+        }
+
+        let order_type = if ascending {
+            OrderType::ascending_nulls_last()
+        } else {
+            OrderType::descending_nulls_last()
+        };
+
+        struct Cursor<'a> {
+            chunk: &'a DataChunk,
+            remaining: std::vec::IntoIter<usize>,
+            row_idx: usize,
+        }
+
+        struct HeapEntry<'a> {
+            cursor: Cursor<'a>,
+            key_col: usize,
+            order_type: OrderType,
+        }
+
+        impl<'a> HeapEntry<'a> {
+            fn key(&self) -> DatumRef<'_> {
+                self.cursor
+                    .chunk
+                    .row_at_unchecked_vis(self.cursor.row_idx)
+                    .datum_at(self.key_col)
+            }
+        }
+
+        impl PartialEq for HeapEntry<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+        impl Eq for HeapEntry<'_> {}
+        impl PartialOrd for HeapEntry<'_> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for HeapEntry<'_> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                // `BinaryHeap` is a max-heap, so reverse the natural order to get the smallest
+                // key out first.
+                cmp_datum(other.key(), self.key(), self.order_type)
+            }
+        }
+
+        let mut heap = BinaryHeap::with_capacity(chunks.len());
+        for chunk in chunks {
+            let mut remaining = chunk.visible_indices().collect_vec().into_iter();
+            if let Some(row_idx) = remaining.next() {
+                heap.push(HeapEntry {
+                    cursor: Cursor {
+                        chunk,
+                        remaining,
+                        row_idx,
+                    },
+                    key_col,
+                    order_type,
+                });
+            }
+        }
+
+        let mut array_builders: Vec<ArrayBuilderImpl> = chunks[0]
+            .columns
+            .iter()
+            .map(|col| col.create_builder(chunks.iter().map(|c| c.cardinality()).sum()))
+            .collect();
+        let mut len = 0;
+        while let Some(HeapEntry { mut cursor, .. }) = heap.pop() {
+            let row = cursor.chunk.row_at_unchecked_vis(cursor.row_idx);
+            for (builder, datum) in array_builders.iter_mut().zip_eq_fast(row.iter()) {
+                builder.append(datum);
+            }
+            len += 1;
+            if let Some(next_row_idx) = cursor.remaining.next() {
+                cursor.row_idx = next_row_idx;
+                heap.push(HeapEntry {
+                    cursor,
+                    key_col,
+                    order_type,
+                });
+            }
+        }
+
+        let new_columns = array_builders
+            .into_iter()
+            .map(|builder| builder.finish().into())
+            .collect();
+        Ok(DataChunk::new(new_columns, len))
+    }
+
     /// Compute hash values for each row.
     pub fn get_hash_values<H: BuildHasher>(
         &self,
@@ -348,16 +588,32 @@ impl DataChunk {
         hasher_builder: H,
     ) -> Vec<HashCode<H>> {
         let mut states = Vec::with_capacity(self.capacity());
+        let mut hash_values = Vec::with_capacity(self.capacity());
+        self.hash_values_into(column_idxes, &hasher_builder, &mut states, &mut hash_values);
+        hash_values.into_iter().map(|value| value.into()).collect_vec()
+    }
+
+    /// Like [`Self::get_hash_values`], but reuses the caller-provided `states` and `out` buffers
+    /// instead of allocating fresh ones on every call. Intended for tight loops (e.g. hash-join
+    /// probing many chunks) where the allocation of a fresh hasher-state `Vec` per chunk
+    /// dominates. Both buffers are resized (not reallocated, when already large enough) to
+    /// `self.capacity()`.
+    pub fn hash_values_into<H: BuildHasher>(
+        &self,
+        column_idxes: &[usize],
+        hasher_builder: &H,
+        states: &mut Vec<H::Hasher>,
+        out: &mut Vec<u64>,
+    ) {
+        states.clear();
         states.resize_with(self.capacity(), || hasher_builder.build_hasher());
         // Compute hash for the specified columns.
         for column_idx in column_idxes {
             let array = self.column_at(*column_idx);
             array.hash_vec(&mut states[..]);
         }
-        finalize_hashers(&mut states[..])
-            .into_iter()
-            .map(|hash_code| hash_code.into())
-            .collect_vec()
+        out.clear();
+        out.extend(states.iter().map(|hasher| hasher.finish()));
     }
 
     /// Random access a tuple in a data chunk. Return in a row format.
@@ -379,6 +635,94 @@ impl DataChunk {
         RowRef::new(self, pos)
     }
 
+    /// Bounds-checked access to a single scalar, as an alternative to indexing `columns()`
+    /// directly (which panics on an out-of-range row or column). `row` is a physical row index,
+    /// i.e. it is not affected by visibility — use [`Self::row_at`] if you also need to know
+    /// whether the row is visible.
+    pub fn scalar_at(&self, row: usize, col: usize) -> ArrayResult<Datum> {
+        let column = self.columns.get(col).ok_or_else(|| {
+            ArrayError::internal(format!(
+                "column index {} out of bounds, chunk has {} columns",
+                col,
+                self.columns.len()
+            ))
+        })?;
+        if row >= self.capacity() {
+            return Err(ArrayError::internal(format!(
+                "row index {} out of bounds, chunk has {} rows",
+                row,
+                self.capacity()
+            )));
+        }
+        Ok(column.datum_at(row))
+    }
+
+    /// Attaches `column` as a new trailing column, for operators (e.g. one adding a computed
+    /// column) that widen a chunk's schema in place instead of destructuring and rebuilding it.
+    /// Errors if `column`'s length does not match this chunk's capacity, since every column
+    /// (visible or not) must agree on physical row count.
+    pub fn append_column(&mut self, column: ArrayRef) -> ArrayResult<()> {
+        if column.len() != self.capacity() {
+            return Err(ArrayError::internal(format!(
+                "cannot append column of length {} to chunk with capacity {}",
+                column.len(),
+                self.capacity()
+            )));
+        }
+        self.columns.push(column);
+        Ok(())
+    }
+
+    /// Compares the visible rows of `self` and `other` for logical equality, i.e. ignoring
+    /// physical layout: a filtered chunk and its [`compact`](Self::compact)ed equivalent compare
+    /// equal as long as their visible rows and values match, in order.
+    pub fn rows_eq(&self, other: &DataChunk) -> bool {
+        self.cardinality() == other.cardinality()
+            && self.rows().zip(other.rows()).all(|(a, b)| Row::eq(&a, b))
+    }
+
+    /// Returns a new chunk containing only the visible rows in `[start, end)`, where `start` and
+    /// `end` count visible rows (i.e. they are bounded by [`Self::cardinality`], not
+    /// [`Self::capacity`]). This narrows the visibility mask in a single pass over the existing
+    /// one instead of rebuilding every column through per-row builders, so it's cheaper than
+    /// `compact`ing and then `rechunk`ing for callers (e.g. windowed operators) that only need a
+    /// contiguous sub-range of rows.
+    pub fn slice(&self, start: usize, end: usize) -> ArrayResult<DataChunk> {
+        if start > end || end > self.cardinality() {
+            return Err(ArrayError::internal(format!(
+                "invalid slice range [{}, {}) for chunk with cardinality {}",
+                start,
+                end,
+                self.cardinality()
+            )));
+        }
+        let mut new_vis = BitmapBuilder::zeroed(self.capacity());
+        let mut visible_idx = 0;
+        for i in 0..self.capacity() {
+            if self.vis2.is_set(i) {
+                if visible_idx >= start && visible_idx < end {
+                    new_vis.set(i, true);
+                }
+                visible_idx += 1;
+            }
+        }
+        Ok(self.with_visibility(new_vis.finish()))
+    }
+
+    /// Returns, for each column in order, the number of visible rows whose value is null.
+    /// Invisible rows are never counted, even if their underlying value happens to be null. Used
+    /// by cardinality/selectivity estimation, e.g. in the `ANALYZE` path.
+    pub fn null_counts(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .map(|column| {
+                (0..self.capacity())
+                    .filter(|&i| self.vis2.is_set(i) && column.is_null(i))
+                    .count()
+            })
+            .collect()
+    }
+
     /// `to_pretty_string` returns a table-like text representation of the `DataChunk`.
     pub fn to_pretty_string(&self) -> String {
         use comfy_table::Table;
@@ -629,6 +973,33 @@ impl DataChunk {
         results
     }
 
+    /// Returns a new column that takes, for each row, the first non-null value among the
+    /// columns at `indices` (in order). The column at the final index is used if all the
+    /// preceding ones are null for that row. All `indices` must refer to columns sharing the
+    /// same data type.
+    ///
+    /// This implements the columnar fast path for `COALESCE(a, b, c, ...)`.
+    pub fn coalesce_columns(&self, indices: &[usize]) -> ArrayResult<ArrayRef> {
+        assert!(!indices.is_empty(), "coalesce requires at least one column");
+        let data_type = self.columns[indices[0]].data_type();
+        for &idx in indices {
+            assert_eq!(
+                self.columns[idx].data_type(),
+                data_type,
+                "coalesce_columns requires all columns to share the same type"
+            );
+        }
+
+        let mut builder = data_type.create_array_builder(self.capacity());
+        for row_idx in 0..self.capacity() {
+            let datum = indices
+                .iter()
+                .find_map(|&idx| self.columns[idx].value_at(row_idx));
+            builder.append(datum);
+        }
+        Ok(builder.finish().into())
+    }
+
     /// Estimate size of hash keys. Their indices in a row are indicated by `column_indices`.
     /// Size here refers to the number of u8s required to store the serialized datum.
     pub fn estimate_value_encoding_size(&self, column_indices: &[usize]) -> usize {
@@ -903,8 +1274,12 @@ impl DataChunkTestExt for DataChunk {
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use crate::array::*;
+    use crate::buffer::Bitmap;
     use crate::row::Row;
+    use crate::types::{DataType, Datum};
 
     #[test]
     fn test_rechunk() {
@@ -963,6 +1338,42 @@ mod tests {
         test_case(10, 10, 7);
     }
 
+    #[test]
+    fn test_rechunk_reversed() {
+        let num_chunks = 10;
+        let chunk_size = 5;
+        let mut chunks = vec![];
+        for chunk_idx in 0..num_chunks {
+            let mut builder = PrimitiveArrayBuilder::<i32>::new(0);
+            for i in chunk_size * chunk_idx..chunk_size * (chunk_idx + 1) {
+                builder.append(Some(i as i32));
+            }
+            chunks.push(DataChunk::new(
+                vec![Arc::new(builder.finish().into())],
+                chunk_size,
+            ));
+        }
+        let total_size = num_chunks * chunk_size;
+
+        let forward = DataChunk::rechunk(&chunks, 7).unwrap();
+        let forward_values = forward
+            .iter()
+            .flat_map(|chunk| chunk.column_at(0).as_int32().iter().collect_vec())
+            .collect_vec();
+
+        let reversed = DataChunk::rechunk_reversed(&chunks, 7).unwrap();
+        let reversed_values = reversed
+            .iter()
+            .flat_map(|chunk| chunk.column_at(0).as_int32().iter().collect_vec())
+            .collect_vec();
+
+        assert_eq!(forward_values.len(), total_size);
+        assert_eq!(
+            reversed_values,
+            forward_values.into_iter().rev().collect_vec()
+        );
+    }
+
     #[test]
     fn test_chunk_iter() {
         let num_of_columns: usize = 2;
@@ -1046,6 +1457,218 @@ mod tests {
         assert_eq!(chunk.reorder_columns(&[]).cardinality(), 3);
     }
 
+    #[test]
+    fn test_compact_all_visible_reuses_columns() {
+        let mut builder = PrimitiveArrayBuilder::<i32>::new(3);
+        for i in 0..3 {
+            builder.append(Some(i));
+        }
+        let column: ArrayRef = Arc::new(builder.finish().into());
+        let chunk = DataChunk::new(vec![column.clone()], Vis::Bitmap(Bitmap::ones(3)));
+
+        let compacted = chunk.compact();
+
+        assert_eq!(compacted.capacity(), 3);
+        assert!(Arc::ptr_eq(&column, compacted.column_at(0)));
+    }
+
+    #[test]
+    fn test_scalar_at() {
+        let mut builder = PrimitiveArrayBuilder::<i32>::new(2);
+        builder.append(Some(1));
+        builder.append(Some(2));
+        let column1: ArrayRef = Arc::new(builder.finish().into());
+
+        let mut builder = PrimitiveArrayBuilder::<i32>::new(2);
+        builder.append(Some(10));
+        builder.append(Some(20));
+        let column2: ArrayRef = Arc::new(builder.finish().into());
+
+        let chunk = DataChunk::new(vec![column1, column2], 2);
+
+        assert_eq!(chunk.scalar_at(0, 0).unwrap(), Some(1i32.into()));
+        assert_eq!(chunk.scalar_at(1, 1).unwrap(), Some(20i32.into()));
+        assert!(chunk.scalar_at(2, 0).is_err());
+        assert!(chunk.scalar_at(0, 2).is_err());
+    }
+
+    #[test]
+    fn test_rows_eq() {
+        let mut builder = PrimitiveArrayBuilder::<i32>::new(4);
+        for i in [1, 2, 3, 4] {
+            builder.append(Some(i));
+        }
+        let column: ArrayRef = Arc::new(builder.finish().into());
+        // Hides row index 1 (value 2), leaving visible rows [1, 3, 4].
+        let visibility = Bitmap::from_iter([true, false, true, true]);
+        let filtered = DataChunk::new(vec![column], visibility);
+        let compacted = filtered.clone().compact();
+
+        assert_ne!(filtered.capacity(), compacted.capacity());
+        assert!(filtered.rows_eq(&compacted));
+        assert!(compacted.rows_eq(&filtered));
+
+        let mut other_builder = PrimitiveArrayBuilder::<i32>::new(3);
+        for i in [1, 3, 5] {
+            other_builder.append(Some(i));
+        }
+        let other_column: ArrayRef = Arc::new(other_builder.finish().into());
+        let other = DataChunk::new(vec![other_column], 3);
+        assert!(!filtered.rows_eq(&other));
+    }
+
+    #[test]
+    fn test_slice() {
+        let chunk = DataChunk::from_pretty(
+            "I
+             0
+             1
+             2
+             3
+             4
+             5
+             6
+             7
+             8
+             9",
+        );
+
+        let middle = chunk.slice(2, 5).unwrap();
+        assert_eq!(middle.cardinality(), 3);
+        assert!(middle.rows_eq(&DataChunk::from_pretty(
+            "I
+             2
+             3
+             4",
+        )));
+
+        let empty = chunk.slice(4, 4).unwrap();
+        assert_eq!(empty.cardinality(), 0);
+
+        let full = chunk.slice(0, 10).unwrap();
+        assert!(full.rows_eq(&chunk));
+
+        assert!(chunk.slice(5, 2).is_err());
+        assert!(chunk.slice(0, 11).is_err());
+    }
+
+    #[test]
+    fn test_null_counts() {
+        let chunk = DataChunk::from_pretty(
+            "I I
+             1 1
+             . 2
+             . 3    D
+             . 4
+             3 5
+             4 6",
+        );
+
+        // Column 0 has two nulls among visible rows (rows 1 and 3); the null in the invisible
+        // row 2 must not be counted. Column 1 has no nulls at all.
+        assert_eq!(chunk.null_counts(), vec![2, 0]);
+    }
+
+    #[test]
+    fn test_append_column() {
+        let mut chunk = DataChunk::from_pretty(
+            "I
+             1
+             2
+             3
+             4
+             5",
+        );
+        assert_eq!(chunk.columns().len(), 1);
+
+        let mut computed = PrimitiveArrayBuilder::<i32>::new(5);
+        for i in [10, 20, 30, 40, 50] {
+            computed.append(Some(i));
+        }
+        let computed: ArrayRef = Arc::new(computed.finish().into());
+        chunk.append_column(computed).unwrap();
+
+        assert_eq!(chunk.columns().len(), 2);
+        assert!(chunk.rows_eq(&DataChunk::from_pretty(
+            "I i
+             1 10
+             2 20
+             3 30
+             4 40
+             5 50",
+        )));
+
+        let mismatched = PrimitiveArrayBuilder::<i32>::new(0).finish();
+        let mismatched: ArrayRef = Arc::new(mismatched.into());
+        assert!(chunk.append_column(mismatched).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "DataChunk column 0 has length 3 but visibility has length 2")]
+    fn test_assert_valid_catches_mismatched_column_length() {
+        let mut builder = PrimitiveArrayBuilder::<i32>::new(3);
+        for i in 0..3 {
+            builder.append(Some(i));
+        }
+        let column: ArrayRef = Arc::new(builder.finish().into());
+        // Bypass `DataChunk::new`'s constructor check to build an intentionally malformed chunk
+        // directly, then verify `assert_valid` catches it.
+        let chunk = DataChunk {
+            columns: vec![column],
+            vis2: Vis::Compact(2),
+        };
+        chunk.assert_valid();
+    }
+
+    #[test]
+    fn test_coalesce_columns() {
+        let chunk = DataChunk::from_pretty(
+            "I I I
+             1 . .
+             . 2 .
+             . . 3
+             . . .",
+        );
+        let coalesced = chunk.coalesce_columns(&[0, 1, 2]).unwrap();
+        assert_eq!(
+            coalesced.as_int64().iter().collect_vec(),
+            vec![Some(1), Some(2), Some(3), None]
+        );
+    }
+
+    #[test]
+    fn test_try_from_rows() {
+        let rows: Vec<Vec<Datum>> = vec![
+            vec![Some(1.into()), Some("a".into())],
+            vec![None, Some("b".into())],
+            vec![Some(3.into()), None],
+        ];
+        let chunk =
+            DataChunk::try_from_rows(&rows, &[DataType::Int32, DataType::Varchar]).unwrap();
+
+        assert_eq!(chunk.capacity(), 3);
+        assert_eq!(
+            chunk.column_at(0).as_int32().iter().collect_vec(),
+            vec![Some(1), None, Some(3)]
+        );
+        assert_eq!(
+            chunk.column_at(1).as_utf8().iter().collect_vec(),
+            vec![Some("a"), Some("b"), None]
+        );
+    }
+
+    #[test]
+    fn test_try_from_rows_width_mismatch() {
+        let rows: Vec<Vec<Datum>> = vec![vec![Some(1.into())]];
+        assert!(DataChunk::try_from_rows(&rows, &[DataType::Int32, DataType::Varchar]).is_err());
+    }
+
+    #[test]
+    fn test_try_from_rows_type_mismatch() {
+        let rows: Vec<Vec<Datum>> = vec![vec![Some("not an int".into())]];
+        assert!(DataChunk::try_from_rows(&rows, &[DataType::Int32]).is_err());
+    }
+
     #[test]
     fn test_chunk_estimated_size() {
         assert_eq!(
@@ -1069,4 +1692,139 @@ mod tests {
             .estimated_heap_size()
         );
     }
+
+    #[test]
+    fn test_to_protobuf_projected() {
+        let chunk = DataChunk::from_pretty(
+            "I I I
+             1 5 2
+             2 9 4
+             3 9 6",
+        );
+
+        let proto = chunk.to_protobuf_projected(&[0, 2]);
+        assert_eq!(proto.columns.len(), 2);
+        assert_eq!(proto.cardinality, 3);
+
+        let decoded = DataChunk::from_protobuf(&proto).unwrap();
+        assert_eq!(
+            decoded,
+            DataChunk::from_pretty(
+                "I I
+                 1 2
+                 2 4
+                 3 6",
+            )
+        );
+    }
+
+    #[test]
+    fn test_from_protobuf_rejects_cardinality_mismatch() {
+        use risingwave_pb::data::PbDataChunk;
+
+        use crate::types::StructType;
+
+        // Struct arrays derive their length from their own null bitmap rather than from the
+        // cardinality passed down by `DataChunk::from_protobuf`, so a struct column whose bitmap
+        // disagrees with the chunk's stated cardinality would otherwise decode into a silently
+        // corrupt chunk.
+        let mut field_builder = PrimitiveArrayBuilder::<i32>::new(3);
+        for i in [1, 2, 3] {
+            field_builder.append(Some(i));
+        }
+        let field: ArrayRef = Arc::new(field_builder.finish().into());
+        let struct_array = StructArray::new(
+            StructType::unnamed(vec![DataType::Int32]),
+            vec![field],
+            Bitmap::from_iter([true, true, true]),
+        );
+        let proto = PbDataChunk {
+            cardinality: 2,
+            columns: vec![ArrayImpl::from(struct_array).to_protobuf()],
+        };
+
+        let err = DataChunk::from_protobuf(&proto).unwrap_err();
+        assert!(err.to_string().contains("column 0 has length 3"));
+        assert!(err.to_string().contains("stated cardinality is 2"));
+    }
+
+    #[test]
+    fn test_hash_values_into_matches_get_hash_values() {
+        use crate::util::hash_util::Crc32FastBuilder;
+
+        let chunk0 = DataChunk::from_pretty(
+            "I I
+             1 2
+             2 4
+             3 6",
+        );
+        let chunk1 = DataChunk::from_pretty(
+            "I I
+             1 2
+             2 4",
+        );
+
+        let mut states = Vec::new();
+        let mut reused = Vec::new();
+        for chunk in [&chunk0, &chunk1] {
+            let expected = chunk.get_hash_values(&[0, 1], Crc32FastBuilder);
+            chunk.hash_values_into(&[0, 1], &Crc32FastBuilder, &mut states, &mut reused);
+            let reused: Vec<_> = reused.iter().map(|&value| value.into()).collect();
+            assert_eq!(expected, reused);
+        }
+    }
+
+    #[test]
+    fn test_visible_indices() {
+        let chunk = DataChunk::from_pretty(
+            "I
+             1 D
+             2
+             3 D
+             4",
+        );
+        assert_eq!(chunk.visible_indices().collect_vec(), vec![1, 3]);
+
+        let chunk = DataChunk::from_pretty(
+            "I
+             1
+             2
+             3",
+        );
+        assert_eq!(
+            chunk.visible_indices().collect_vec(),
+            (0..chunk.cardinality()).collect_vec()
+        );
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let make_chunk = |values: &[i32]| {
+            let mut builder = PrimitiveArrayBuilder::<i32>::new(0);
+            for v in values {
+                builder.append(Some(*v));
+            }
+            DataChunk::new(vec![Arc::new(builder.finish().into())], values.len())
+        };
+
+        let chunks = vec![
+            make_chunk(&[1, 4, 9]),
+            make_chunk(&[2, 2, 8]),
+            make_chunk(&[0, 5]),
+        ];
+
+        let merged = DataChunk::merge_sorted(&chunks, 0, true).unwrap();
+        let values = merged.column_at(0).as_int32().iter().collect_vec();
+        let mut expected = chunks
+            .iter()
+            .flat_map(|chunk| chunk.column_at(0).as_int32().iter().collect_vec())
+            .collect_vec();
+        expected.sort();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn test_merge_sorted_empty_input_errs() {
+        assert!(DataChunk::merge_sorted(&[], 0, true).is_err());
+    }
 }
@@ -34,7 +34,7 @@ use crate::util::epoch::Epoch;
 
 // This is a hack, &'static str is not allowed as a const generics argument.
 // TODO: refine this using the adt_const_params feature.
-const CONFIG_KEYS: [&str; 28] = [
+const CONFIG_KEYS: [&str; 29] = [
     "RW_IMPLICIT_FLUSH",
     "CREATE_COMPACTION_GROUP_FOR_MV",
     "QUERY_MODE",
@@ -63,6 +63,7 @@ const CONFIG_KEYS: [&str; 28] = [
     "RW_FORCE_SPLIT_DISTINCT_AGG",
     "CLIENT_MIN_MESSAGES",
     "CLIENT_ENCODING",
+    "SYNC_COMMIT",
 ];
 
 // MUST HAVE 1v1 relationship to CONFIG_KEYS. e.g. CONFIG_KEYS[IMPLICIT_FLUSH] =
@@ -95,6 +96,7 @@ const SERVER_VERSION_NUM: usize = 24;
 const FORCE_SPLIT_DISTINCT_AGG: usize = 25;
 const CLIENT_MIN_MESSAGES: usize = 26;
 const CLIENT_ENCODING: usize = 27;
+const SYNC_COMMIT: usize = 28;
 
 trait ConfigEntry: Default + for<'a> TryFrom<&'a [&'a str], Error = RwError> {
     fn entry_name() -> &'static str;
@@ -303,6 +305,7 @@ type ServerVersionNum = ConfigI32<SERVER_VERSION_NUM, 80_300>;
 type ForceSplitDistinctAgg = ConfigBool<FORCE_SPLIT_DISTINCT_AGG, false>;
 type ClientMinMessages = ConfigString<CLIENT_MIN_MESSAGES>;
 type ClientEncoding = ConfigString<CLIENT_ENCODING>;
+type SyncCommit = ConfigBool<SYNC_COMMIT, false>;
 
 /// Report status or notice to caller.
 pub trait ConfigReporter {
@@ -410,6 +413,14 @@ pub struct ConfigMap {
     /// see <https://www.postgresql.org/docs/15/runtime-config-client.html#GUC-CLIENT-ENCODING>
     #[educe(Default(expression = "ConfigString::<CLIENT_ENCODING>(String::from(\"UTF8\"))"))]
     client_encoding: ClientEncoding,
+
+    /// If `SYNC_COMMIT` is on, then every flush (the implicit one that `RW_IMPLICIT_FLUSH` runs
+    /// after an INSERT/UPDATE/DELETE statement, or an explicit `FLUSH`) will also wait for the
+    /// committed epoch to be confirmed by meta before returning. It has no effect on DML
+    /// statements while `RW_IMPLICIT_FLUSH` is off. This closes the gap where `RW_IMPLICIT_FLUSH` only
+    /// guarantees visibility within the current frontend node: a later statement issued against a
+    /// different frontend could otherwise still miss the write.
+    sync_commit: SyncCommit,
 }
 
 impl ConfigMap {
@@ -496,6 +507,8 @@ impl ConfigMap {
                 .into());
             }
             // No actual assignment because we only support UTF8.
+        } else if key.eq_ignore_ascii_case(SyncCommit::entry_name()) {
+            self.sync_commit = val.as_slice().try_into()?;
         } else {
             return Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into());
         }
@@ -562,6 +575,8 @@ impl ConfigMap {
             Ok(self.client_min_messages.to_string())
         } else if key.eq_ignore_ascii_case(ClientEncoding::entry_name()) {
             Ok(self.client_encoding.to_string())
+        } else if key.eq_ignore_ascii_case(SyncCommit::entry_name()) {
+            Ok(self.sync_commit.to_string())
         } else {
             Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into())
         }
@@ -704,6 +719,11 @@ impl ConfigMap {
                 setting : self.client_encoding.to_string(),
                 description : String::from("Sets the client's character set encoding.")
             },
+            VariableInfo{
+                name : SyncCommit::entry_name().to_lowercase(),
+                setting : self.sync_commit.to_string(),
+                description : String::from("If `SYNC_COMMIT` is on, then every flush (including the implicit flush after an INSERT/UPDATE/DELETE statement when `RW_IMPLICIT_FLUSH` is on) will also wait for the committed epoch to be confirmed by meta before returning.")
+            },
         ]
     }
 
@@ -819,4 +839,8 @@ impl ConfigMap {
     pub fn get_client_encoding(&self) -> &str {
         &self.client_encoding
     }
+
+    pub fn get_sync_commit(&self) -> bool {
+        *self.sync_commit
+    }
 }
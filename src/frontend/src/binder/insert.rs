@@ -19,7 +19,7 @@ use risingwave_common::catalog::{ColumnCatalog, Schema, TableVersionId};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::types::DataType;
 use risingwave_common::util::iter_util::ZipEqFast;
-use risingwave_sqlparser::ast::{Ident, ObjectName, Query, SelectItem};
+use risingwave_sqlparser::ast::{Ident, ObjectName, OnConflict, Query, SelectItem};
 
 use super::statement::RewriteExprsRecursive;
 use super::BoundQuery;
@@ -99,8 +99,25 @@ impl Binder {
         name: ObjectName,
         cols_to_insert_by_user: Vec<Ident>,
         source: Query,
+        on_conflict: Option<OnConflict>,
         returning_items: Vec<SelectItem>,
     ) -> Result<BoundInsert> {
+        if let Some(on_conflict) = on_conflict {
+            // The parser accepts `ON CONFLICT DO NOTHING`/`DO UPDATE` (see `sqlparser`), but the
+            // upsert execution semantics (deduplicating against the target table's key and
+            // reporting a combined inserted+updated row count) aren't implemented yet, so binding
+            // still rejects the clause outright rather than silently ignoring it.
+            //
+            // TODO: this only covers parsing the clause; actually planning and executing upsert
+            // semantics against the target table's primary key is still unimplemented and tracked
+            // as separate, open follow-up work, not something this rejection closes out.
+            let clause = match on_conflict {
+                OnConflict::DoNothing => "ON CONFLICT DO NOTHING",
+                OnConflict::DoUpdate(_) => "ON CONFLICT DO UPDATE",
+            };
+            return Err(ErrorCode::NotImplemented(clause.to_string(), None.into()).into());
+        }
+
         let (schema_name, table_name) = Self::resolve_schema_qualified_name(&self.db_name, name)?;
         self.bind_table(schema_name.as_deref(), &table_name, None)?;
 
@@ -47,6 +47,17 @@ impl BoundStatement {
             BoundStatement::Query(q) => q.schema().fields().into(),
         }
     }
+
+    /// The name of the table this statement writes to, for DML statements. `None` for a plain
+    /// `Query`, which has no single target table.
+    pub fn target_table_name(&self) -> Option<&str> {
+        match self {
+            BoundStatement::Insert(i) => Some(&i.table_name),
+            BoundStatement::Delete(d) => Some(&d.table_name),
+            BoundStatement::Update(u) => Some(&u.table_name),
+            BoundStatement::Query(_) => None,
+        }
+    }
 }
 
 impl Binder {
@@ -56,9 +67,10 @@ impl Binder {
                 table_name,
                 columns,
                 source,
+                on_conflict,
                 returning,
             } => Ok(BoundStatement::Insert(
-                self.bind_insert(table_name, columns, *source, returning)?
+                self.bind_insert(table_name, columns, *source, on_conflict, returning)?
                     .into(),
             )),
 
@@ -12,13 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::Result;
 
 use super::RwPgResponse;
 use crate::handler::HandlerArgs;
+use crate::meta_client::FrontendMetaClient;
 use crate::session::SessionImpl;
 
+/// Interval between polls in [`wait_for_committed_epoch`].
+const SYNC_COMMIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 pub(super) async fn handle_flush(handler_args: HandlerArgs) -> Result<RwPgResponse> {
     do_flush(&handler_args.session).await?;
     Ok(PgResponse::empty_result(StatementType::FLUSH))
@@ -33,8 +39,145 @@ pub(crate) async fn do_flush(session: &SessionImpl) -> Result<()> {
     session
         .env()
         .hummock_snapshot_manager()
-        .wait(snapshot)
+        .wait(snapshot.clone())
         .await;
 
+    // `RW_IMPLICIT_FLUSH`'s wait above only guarantees visibility within this frontend node. If
+    // `SYNC_COMMIT` is also on, confirm with meta directly that the epoch has been committed
+    // before returning, so that a statement issued against a different frontend afterwards is
+    // guaranteed to see this write as well.
+    if session.config().get_sync_commit() {
+        wait_for_committed_epoch(client, snapshot.committed_epoch).await?;
+    }
+
     Ok(())
 }
+
+/// Polls `client.get_snapshot()` until its `committed_epoch` reaches `min_committed_epoch`.
+async fn wait_for_committed_epoch(
+    client: &dyn FrontendMetaClient,
+    min_committed_epoch: u64,
+) -> Result<()> {
+    loop {
+        let snapshot = client.get_snapshot().await?;
+        if snapshot.committed_epoch >= min_committed_epoch {
+            return Ok(());
+        }
+        tokio::time::sleep(SYNC_COMMIT_POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use risingwave_common::system_param::reader::SystemParamsReader;
+    use risingwave_pb::backup_service::MetaSnapshotMetadata;
+    use risingwave_pb::ddl_service::DdlProgress;
+    use risingwave_pb::hummock::HummockSnapshot;
+    use risingwave_pb::meta::list_actor_states_response::ActorState;
+    use risingwave_pb::meta::list_fragment_distribution_response::FragmentDistribution;
+    use risingwave_pb::meta::list_table_fragment_states_response::TableFragmentState;
+    use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
+    use risingwave_pb::meta::CreatingJobInfo;
+    use risingwave_rpc_client::error::Result as RpcResult;
+
+    use super::*;
+
+    /// A [`FrontendMetaClient`] whose `committed_epoch` can be advanced from the test body, to
+    /// simulate meta confirming a commit after some delay.
+    struct DelayedCommitMetaClient {
+        committed_epoch: AtomicU64,
+    }
+
+    #[async_trait::async_trait]
+    impl FrontendMetaClient for DelayedCommitMetaClient {
+        async fn pin_snapshot(&self) -> RpcResult<HummockSnapshot> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn get_snapshot(&self) -> RpcResult<HummockSnapshot> {
+            Ok(HummockSnapshot {
+                committed_epoch: self.committed_epoch.load(Ordering::SeqCst),
+                current_epoch: 0,
+            })
+        }
+
+        async fn flush(&self, _checkpoint: bool) -> RpcResult<HummockSnapshot> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn cancel_creating_jobs(&self, _infos: Vec<CreatingJobInfo>) -> RpcResult<()> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn list_table_fragments(
+            &self,
+            _table_ids: &[u32],
+        ) -> RpcResult<HashMap<u32, TableFragmentInfo>> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn list_table_fragment_states(&self) -> RpcResult<Vec<TableFragmentState>> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn list_fragment_distribution(&self) -> RpcResult<Vec<FragmentDistribution>> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn list_actor_states(&self) -> RpcResult<Vec<ActorState>> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn unpin_snapshot(&self) -> RpcResult<()> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn unpin_snapshot_before(&self, _epoch: u64) -> RpcResult<()> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn list_meta_snapshots(&self) -> RpcResult<Vec<MetaSnapshotMetadata>> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn get_system_params(&self) -> RpcResult<SystemParamsReader> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn set_system_param(
+            &self,
+            _param: String,
+            _value: Option<String>,
+        ) -> RpcResult<Option<SystemParamsReader>> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+
+        async fn list_ddl_progress(&self) -> RpcResult<Vec<DdlProgress>> {
+            unreachable!("not used by wait_for_committed_epoch")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_committed_epoch_blocks_until_confirmed() {
+        let client = Arc::new(DelayedCommitMetaClient {
+            committed_epoch: AtomicU64::new(0),
+        });
+
+        let waiter = {
+            let client = client.clone();
+            tokio::spawn(async move { wait_for_committed_epoch(client.as_ref(), 10).await })
+        };
+
+        // The epoch hasn't been confirmed yet, so the waiter must not have resolved.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        client.committed_epoch.store(10, Ordering::SeqCst);
+        waiter.await.unwrap().unwrap();
+    }
+}
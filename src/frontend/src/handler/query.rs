@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -168,11 +169,17 @@ fn gen_batch_query_plan(
         ..
     } = bind_result;
 
+    let target_table_name = bound.target_table_name().map(|name| name.to_owned());
+
     let mut planner = Planner::new(context);
 
-    let mut logical = planner.plan(bound)?;
+    let mut logical = planner
+        .plan(bound)
+        .map_err(|e| attach_target_table(e, &target_table_name))?;
     let schema = logical.schema();
-    let batch_plan = logical.gen_batch_plan()?;
+    let batch_plan = logical
+        .gen_batch_plan()
+        .map_err(|e| attach_target_table(e, &target_table_name))?;
 
     let dependent_relations =
         RelationCollectorVisitor::collect_with(dependent_relations, batch_plan.clone());
@@ -210,6 +217,18 @@ fn gen_batch_query_plan(
     })
 }
 
+/// Prefixes a planning failure with the DML statement's target table, if any, so logs and client
+/// responses point at the actual failing statement instead of a bare planner error.
+fn attach_target_table(err: RwError, target_table_name: &Option<String>) -> RwError {
+    match target_table_name {
+        Some(name) => {
+            ErrorCode::InternalError(format!("failed to plan statement on table `{name}`: {err}"))
+                .into()
+        }
+        None => err,
+    }
+}
+
 fn must_run_in_distributed_mode(stmt: &Statement) -> Result<bool> {
     fn is_insert_using_select(stmt: &Statement) -> bool {
         fn has_select_query(set_expr: &SetExpr) -> bool {
@@ -357,42 +376,64 @@ async fn execute(
         }
     };
 
+    // For `RETURNING` statements, the affected row count is the number of rows actually
+    // returned, which isn't known until the result stream has been fully read. Count rows as
+    // they're forwarded downstream instead of buffering the whole stream into memory, so peak
+    // memory stays bounded by a single chunk; `returning_row_cnt` is only consulted once the
+    // stream is drained (see `callback` below, which `PgResponse` always runs after the caller
+    // has exhausted `values_stream`).
+    let returning_row_cnt = Arc::new(AtomicI32::new(0));
+
     let row_cnt: Option<i32> = match stmt_type {
-        StatementType::SELECT
-        | StatementType::INSERT_RETURNING
+        StatementType::SELECT => None,
+
+        StatementType::INSERT_RETURNING
         | StatementType::DELETE_RETURNING
-        | StatementType::UPDATE_RETURNING => None,
+        | StatementType::UPDATE_RETURNING => {
+            let returning_row_cnt = returning_row_cnt.clone();
+            row_stream = PgResponseStream::Rows(
+                row_stream
+                    .inspect(move |row_set| {
+                        if let Ok(row_set) = row_set {
+                            returning_row_cnt.fetch_add(row_set.len() as i32, Ordering::Relaxed);
+                        }
+                    })
+                    .boxed(),
+            );
+            None
+        }
 
         StatementType::INSERT | StatementType::DELETE | StatementType::UPDATE => {
-            let first_row_set = row_stream.next().await;
-            let first_row_set = match first_row_set {
-                None => {
-                    return Err(RwError::from(ErrorCode::InternalError(
-                        "no affected rows in output".to_string(),
-                    )))
-                }
-                Some(row) => {
-                    row.map_err(|err| RwError::from(ErrorCode::InternalError(format!("{}", err))))?
+            // Every parallel task of the batch plan emits its own row carrying the affected row
+            // count of the partition it processed, so in distributed mode the stream may yield
+            // more than one row. Sum them up as they arrive instead of only looking at the first
+            // one (which would silently drop the counts of the other partitions) or collecting
+            // the whole stream into memory first.
+            // An empty result set can still occur, e.g. when every fragment of the query was
+            // pruned away; `row_cnt` naturally stays zero in that case.
+            let mut row_cnt: i32 = 0;
+            while let Some(row_set) = row_stream.next().await {
+                let row_set =
+                    row_set.map_err(|err| RwError::from(ErrorCode::InternalError(format!("{}", err))))?;
+                for row in &row_set {
+                    let affected_rows_str = row.values()[0]
+                        .as_ref()
+                        .expect("compute node should return affected rows in output");
+                    let partial: i32 = if let Format::Binary = first_field_format {
+                        i64::from_sql(&postgres_types::Type::INT8, affected_rows_str)
+                            .unwrap()
+                            .try_into()
+                            .expect("affected rows count large than i64")
+                    } else {
+                        String::from_utf8(affected_rows_str.to_vec())
+                            .unwrap()
+                            .parse()
+                            .unwrap_or_default()
+                    };
+                    row_cnt += partial;
                 }
-            };
-            let affected_rows_str = first_row_set[0].values()[0]
-                .as_ref()
-                .expect("compute node should return affected rows in output");
-            if let Format::Binary = first_field_format {
-                Some(
-                    i64::from_sql(&postgres_types::Type::INT8, affected_rows_str)
-                        .unwrap()
-                        .try_into()
-                        .expect("affected rows count large than i64"),
-                )
-            } else {
-                Some(
-                    String::from_utf8(affected_rows_str.to_vec())
-                        .unwrap()
-                        .parse()
-                        .unwrap_or_default(),
-                )
             }
+            Some(row_cnt)
         }
         _ => unreachable!(),
     };
@@ -400,8 +441,16 @@ async fn execute(
     // We need to do some post work after the query is finished and before the `Complete` response
     // it sent. This is achieved by the `callback` in `PgResponse`.
     let callback = async move {
-        // Implicitly flush the writes.
-        if session.config().get_implicit_flush() && stmt_type.is_dml() {
+        // Implicitly flush the writes, unless we already know the statement did not change
+        // anything (e.g. `DELETE` matched no rows), in which case there is nothing to wait for
+        // and we can skip the round trip to the meta service. For `RETURNING` statements
+        // `row_cnt` is never set (see above), so fall back to the incrementally-counted total,
+        // which is accurate by the time this callback runs.
+        let skip_flush = match row_cnt {
+            Some(row_cnt) => row_cnt == 0,
+            None => stmt_type.is_returning() && returning_row_cnt.load(Ordering::Relaxed) == 0,
+        };
+        if session.config().get_implicit_flush() && stmt_type.is_dml() && !skip_flush {
             do_flush(&session).await?;
         }
 
@@ -479,3 +528,53 @@ async fn local_execute(session: Arc<SessionImpl>, query: Query) -> Result<LocalQ
 
     Ok(execution.stream_rows())
 }
+
+#[cfg(test)]
+mod tests {
+    use futures_async_stream::for_await;
+    use risingwave_common::error::ErrorCode;
+
+    use super::*;
+    use crate::test_utils::LocalFrontend;
+
+    #[test]
+    fn test_attach_target_table() {
+        let err: RwError = ErrorCode::InternalError("some planner failure".to_owned()).into();
+
+        let attached = attach_target_table(err, &Some("orders".to_owned()));
+        assert!(attached.to_string().contains("orders"));
+        assert!(attached.to_string().contains("some planner failure"));
+
+        // No target table (e.g. a plain `Query`): the error passes through unchanged.
+        let err: RwError = ErrorCode::InternalError("some planner failure".to_owned()).into();
+        let unattached = attach_target_table(err, &None);
+        assert_eq!(unattached.to_string(), "internal error: some planner failure");
+    }
+
+    #[tokio::test]
+    async fn test_update_returning_streams_rows_without_buffering() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v int)").await.unwrap();
+        frontend
+            .run_sql("insert into t values (1), (2)")
+            .await
+            .unwrap();
+        frontend.run_sql("flush").await.unwrap();
+
+        let mut response = frontend
+            .run_sql("update t set v = v + 1 returning v")
+            .await
+            .unwrap();
+        // Unlike a plain (non-`RETURNING`) `UPDATE`, the affected row count for `RETURNING`
+        // statements is never pre-computed: it's only known once the result stream below has
+        // been fully drained, so `PgResponse` doesn't set it up front.
+        assert_eq!(response.affected_rows_cnt(), None);
+
+        let mut returned = vec![];
+        #[for_await]
+        for row_set in response.values_stream() {
+            returned.extend(row_set.unwrap());
+        }
+        assert_eq!(returned.len(), 2);
+    }
+}
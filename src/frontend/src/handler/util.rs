@@ -366,6 +366,22 @@ mod tests {
         assert_eq!(vec, expected);
     }
 
+    #[test]
+    fn test_value_format_int32_text_and_binary() {
+        let static_session = StaticSessionData {
+            timezone: "UTC".into(),
+        };
+        let value = ScalarRefImpl::Int32(87);
+
+        let text = pg_value_format(&DataType::Int32, value, Format::Text, &static_session).unwrap();
+        assert_eq!(&text, "87");
+
+        // Binary int4 is encoded as 4 network-order (big-endian) bytes.
+        let binary =
+            pg_value_format(&DataType::Int32, value, Format::Binary, &static_session).unwrap();
+        assert_eq!(&binary[..], &87_i32.to_be_bytes());
+    }
+
     #[test]
     fn test_value_format() {
         use {DataType as T, ScalarRefImpl as S};
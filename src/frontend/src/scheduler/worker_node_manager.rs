@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock, RwLockReadGuard};
 use std::time::Duration;
 
@@ -31,6 +32,13 @@ pub struct WorkerNodeManager {
     inner: RwLock<WorkerNodeManagerInner>,
     /// Temporarily make worker invisible from serving cluster.
     worker_node_mask: Arc<RwLock<HashSet<u32>>>,
+    /// Bumped whenever worker nodes, vnode mappings or `worker_node_mask` change, so that
+    /// [`WorkerNodeSelector`] can cache resolved fragment mappings without serving stale ones
+    /// across queries.
+    mapping_epoch: Arc<AtomicU64>,
+    /// Caches the fragment mapping resolved by [`WorkerNodeSelector::fragment_mapping`] for
+    /// serving queries, keyed by the epoch it was resolved at.
+    resolved_mapping_cache: RwLock<HashMap<FragmentId, (u64, ParallelUnitMapping)>>,
 }
 
 struct WorkerNodeManagerInner {
@@ -58,6 +66,8 @@ impl WorkerNodeManager {
                 serving_fragment_vnode_mapping: Default::default(),
             }),
             worker_node_mask: Arc::new(Default::default()),
+            mapping_epoch: Arc::new(AtomicU64::new(0)),
+            resolved_mapping_cache: Default::default(),
         }
     }
 
@@ -71,9 +81,39 @@ impl WorkerNodeManager {
         Self {
             inner,
             worker_node_mask: Arc::new(Default::default()),
+            mapping_epoch: Arc::new(AtomicU64::new(0)),
+            resolved_mapping_cache: Default::default(),
         }
     }
 
+    /// Invalidates cached resolved fragment mappings. Called whenever worker nodes, vnode
+    /// mappings, or the worker mask change.
+    fn bump_mapping_epoch(&self) {
+        self.mapping_epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn mapping_epoch(&self) -> u64 {
+        self.mapping_epoch.load(Ordering::Relaxed)
+    }
+
+    /// Returns the resolved fragment mapping cached for the current epoch, if any.
+    fn cached_resolved_mapping(&self, fragment_id: FragmentId) -> Option<ParallelUnitMapping> {
+        let epoch = self.mapping_epoch();
+        let cache = self.resolved_mapping_cache.read().unwrap();
+        cache
+            .get(&fragment_id)
+            .filter(|(cached_epoch, _)| *cached_epoch == epoch)
+            .map(|(_, mapping)| mapping.clone())
+    }
+
+    fn cache_resolved_mapping(&self, fragment_id: FragmentId, mapping: ParallelUnitMapping) {
+        let epoch = self.mapping_epoch();
+        self.resolved_mapping_cache
+            .write()
+            .unwrap()
+            .insert(fragment_id, (epoch, mapping));
+    }
+
     pub fn list_worker_nodes(&self) -> Vec<WorkerNode> {
         self.inner
             .read()
@@ -105,16 +145,22 @@ impl WorkerNodeManager {
         for w in &mut write_guard.worker_nodes {
             if w.id == node.id {
                 *w = node;
+                drop(write_guard);
+                self.bump_mapping_epoch();
                 return;
             }
         }
         // insert
         write_guard.worker_nodes.push(node);
+        drop(write_guard);
+        self.bump_mapping_epoch();
     }
 
     pub fn remove_worker_node(&self, node: WorkerNode) {
         let mut write_guard = self.inner.write().unwrap();
         write_guard.worker_nodes.retain(|x| x.id != node.id);
+        drop(write_guard);
+        self.bump_mapping_epoch();
     }
 
     pub fn refresh(
@@ -136,6 +182,8 @@ impl WorkerNodeManager {
         write_guard.worker_nodes = nodes;
         write_guard.streaming_fragment_vnode_mapping = streaming_mapping;
         write_guard.serving_fragment_vnode_mapping = serving_mapping;
+        drop(write_guard);
+        self.bump_mapping_epoch();
     }
 
     /// If parallel unit ids is empty, the scheduler may fail to schedule any task and stuck at
@@ -187,6 +235,7 @@ impl WorkerNodeManager {
             .streaming_fragment_vnode_mapping
             .try_insert(fragment_id, vnode_mapping)
             .unwrap();
+        self.bump_mapping_epoch();
     }
 
     pub fn update_streaming_fragment_mapping(
@@ -199,6 +248,8 @@ impl WorkerNodeManager {
             .streaming_fragment_vnode_mapping
             .insert(fragment_id, vnode_mapping)
             .unwrap();
+        drop(guard);
+        self.bump_mapping_epoch();
     }
 
     pub fn remove_streaming_fragment_mapping(&self, fragment_id: &FragmentId) {
@@ -207,6 +258,8 @@ impl WorkerNodeManager {
             .streaming_fragment_vnode_mapping
             .remove(fragment_id)
             .unwrap();
+        drop(guard);
+        self.bump_mapping_epoch();
     }
 
     /// Returns fragment's vnode mapping for serving.
@@ -228,6 +281,8 @@ impl WorkerNodeManager {
             mappings.keys()
         );
         guard.serving_fragment_vnode_mapping = mappings;
+        drop(guard);
+        self.bump_mapping_epoch();
     }
 
     pub fn upsert_serving_fragment_mapping(
@@ -244,6 +299,8 @@ impl WorkerNodeManager {
                 .serving_fragment_vnode_mapping
                 .insert(fragment_id, mapping);
         }
+        drop(guard);
+        self.bump_mapping_epoch();
     }
 
     pub fn remove_serving_fragment_mapping(&self, fragment_ids: &[FragmentId]) {
@@ -255,6 +312,8 @@ impl WorkerNodeManager {
         for fragment_id in fragment_ids {
             guard.serving_fragment_vnode_mapping.remove(fragment_id);
         }
+        drop(guard);
+        self.bump_mapping_epoch();
     }
 
     fn worker_node_mask(&self) -> RwLockReadGuard<'_, HashSet<u32>> {
@@ -267,13 +326,17 @@ impl WorkerNodeManager {
             return;
         }
         worker_node_mask.insert(worker_node_id);
+        drop(worker_node_mask);
+        self.bump_mapping_epoch();
         let worker_node_mask_ref = self.worker_node_mask.clone();
+        let mapping_epoch_ref = self.mapping_epoch.clone();
         tokio::spawn(async move {
             tokio::time::sleep(duration).await;
             worker_node_mask_ref
                 .write()
                 .unwrap()
                 .remove(&worker_node_id);
+            mapping_epoch_ref.fetch_add(1, Ordering::Relaxed);
         });
     }
 }
@@ -329,10 +392,17 @@ impl WorkerNodeSelector {
         if self.enable_barrier_read {
             self.manager.get_streaming_fragment_mapping(&fragment_id)
         } else {
+            // Repeated lookups for the same fragment (e.g. consecutive DML statements against the
+            // same table) are common, so cache the resolved mapping until anything that could
+            // change it (worker nodes, vnode mappings or the worker mask) bumps the epoch.
+            if let Some(cached) = self.manager.cached_resolved_mapping(fragment_id) {
+                return Ok(cached);
+            }
             let (hint, parallelism) = match self.manager.serving_fragment_mapping(fragment_id) {
                 Ok(o) => {
                     if self.manager.worker_node_mask().is_empty() {
                         // 1. Stable mapping for most cases.
+                        self.manager.cache_resolved_mapping(fragment_id, o.clone());
                         return Ok(o);
                     }
                     let max_parallelism = o.iter_unique().count();
@@ -355,8 +425,11 @@ impl WorkerNodeSelector {
             };
             // 2. Temporary mapping that filters out unavailable workers.
             let new_workers = self.apply_worker_node_mask(self.manager.list_serving_worker_nodes());
-            let masked_mapping = place_vnode(hint.as_ref(), &new_workers, parallelism);
-            masked_mapping.ok_or_else(|| SchedulerError::EmptyWorkerNodes)
+            let masked_mapping = place_vnode(hint.as_ref(), &new_workers, parallelism)
+                .ok_or_else(|| SchedulerError::EmptyWorkerNodes)?;
+            self.manager
+                .cache_resolved_mapping(fragment_id, masked_mapping.clone());
+            Ok(masked_mapping)
         }
     }
 
@@ -444,4 +517,36 @@ mod tests {
             worker_nodes.as_slice()[1..].to_vec()
         );
     }
+
+    #[test]
+    fn test_fragment_mapping_cache() {
+        use risingwave_common::hash::ParallelUnitMapping;
+
+        use super::*;
+
+        let fragment_id = 0;
+        let mapping = ParallelUnitMapping::new_single(0);
+
+        let manager = Arc::new(WorkerNodeManager::mock(vec![]));
+        manager.set_serving_fragment_mapping(HashMap::from([(fragment_id, mapping.clone())]));
+        let selector = WorkerNodeSelector::new(manager.clone(), false);
+
+        // The first lookup resolves and caches the mapping.
+        let resolved = selector.fragment_mapping(fragment_id).unwrap();
+        assert_eq!(resolved, mapping);
+        let epoch_after_first_lookup = manager.mapping_epoch();
+
+        // A second lookup for the same fragment is served from the cache, at the same epoch.
+        let resolved_again = selector.fragment_mapping(fragment_id).unwrap();
+        assert_eq!(resolved_again, mapping);
+        assert_eq!(manager.mapping_epoch(), epoch_after_first_lookup);
+
+        // Updating the mapping invalidates the cache and is reflected on the next lookup.
+        let new_mapping = ParallelUnitMapping::new_single(1);
+        manager
+            .upsert_serving_fragment_mapping(HashMap::from([(fragment_id, new_mapping.clone())]));
+        assert!(manager.mapping_epoch() > epoch_after_first_lookup);
+        let resolved_after_update = selector.fragment_mapping(fragment_id).unwrap();
+        assert_eq!(resolved_after_update, new_mapping);
+    }
 }
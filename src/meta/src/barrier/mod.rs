@@ -70,6 +70,8 @@ mod trace;
 
 pub use self::command::{Command, Reschedule};
 pub use self::schedule::BarrierScheduler;
+#[cfg(test)]
+pub(crate) use self::schedule::ScheduledBarriers;
 pub use self::trace::TracedEpoch;
 
 /// Status of barrier manager.
@@ -305,6 +305,18 @@ impl ScheduledBarriers {
         rx.changed().await.unwrap();
     }
 
+    /// Pop one scheduled barrier and immediately acknowledge it as collected, without actually
+    /// running it. Used to unblock [`BarrierScheduler::flush`] in tests that exercise the
+    /// scheduling queue without a real [`super::GlobalBarrierManager`] consuming it.
+    #[cfg(test)]
+    pub(crate) async fn collect_one_for_test(&self) {
+        self.wait_one().await;
+        let scheduled = self.pop_or_default().await;
+        for mut notifier in scheduled.notifiers {
+            notifier.notify_collected();
+        }
+    }
+
     /// Clear all queued scheduled barriers, and notify their subscribers with failed as aborted.
     pub(super) async fn abort(&self) {
         let mut queue = self.inner.queue.write().await;
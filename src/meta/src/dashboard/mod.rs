@@ -18,25 +18,36 @@ mod proxy;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use axum::body::Body;
-use axum::extract::{Extension, Path};
+use axum::extract::{Extension, Path, Query};
 use axum::http::{Method, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::IntoResponse;
-use axum::routing::{get, get_service};
+use axum::routing::{get, get_service, post};
 use axum::Router;
+use futures::stream::{Stream, StreamExt};
 use hyper::Request;
 use parking_lot::Mutex;
 use risingwave_rpc_client::ComputeClientPool;
+use tokio_stream::wrappers::IntervalStream;
 use tower::ServiceBuilder;
 use tower_http::add_extension::AddExtensionLayer;
 use tower_http::cors::{self, CorsLayer};
 use tower_http::services::ServeDir;
 
+use crate::barrier::BarrierScheduler;
+use crate::error::MetaError;
+use crate::hummock::HummockManagerRef;
 use crate::manager::{ClusterManagerRef, FragmentManagerRef};
 use crate::storage::MetaStore;
 
+/// Default polling interval for [`handlers::stream_actors`], used when a [`DashboardService`]
+/// doesn't need a different cadence.
+pub const DEFAULT_ACTOR_STREAM_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Clone)]
 pub struct DashboardService<S: MetaStore> {
     pub dashboard_addr: SocketAddr,
@@ -44,7 +55,11 @@ pub struct DashboardService<S: MetaStore> {
     pub prometheus_client: Option<prometheus_http_query::Client>,
     pub cluster_manager: ClusterManagerRef<S>,
     pub fragment_manager: FragmentManagerRef<S>,
+    pub hummock_manager: HummockManagerRef<S>,
     pub compute_clients: ComputeClientPool,
+    pub barrier_scheduler: BarrierScheduler<S>,
+    /// Polling interval for the `/actors/stream` SSE endpoint.
+    pub actor_stream_interval: Duration,
 
     // TODO: replace with catalog manager.
     pub meta_store: Arc<S>,
@@ -68,23 +83,66 @@ pub(super) mod handlers {
     use crate::manager::WorkerId;
     use crate::model::TableFragments;
 
-    pub struct DashboardError(anyhow::Error);
+    /// A dashboard API error response, serialized as `{"code": <int>, "message": <string>}`
+    /// where `code` is the numeric HTTP status, so a client can branch on `code` instead of
+    /// pattern-matching the free-form `message`, and so that not every failure has to be
+    /// reported as a 500.
+    pub struct DashboardError {
+        status: StatusCode,
+        inner: anyhow::Error,
+    }
+
     pub type Result<T> = std::result::Result<T, DashboardError>;
     type TableId = i32;
     type TableActors = (TableId, Vec<StreamActor>);
 
+    /// A downstream meta-store/RPC failure, or any other unexpected error encountered while
+    /// serving the request. Maps to `500 Internal Server Error`.
     pub fn err(err: impl Into<anyhow::Error>) -> DashboardError {
-        DashboardError(err.into())
+        DashboardError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            inner: err.into(),
+        }
+    }
+
+    /// Like [`err`], but for a request that was malformed, e.g. an out-of-range enum value in a
+    /// path/query parameter. Maps to `400 Bad Request`.
+    pub fn invalid_parameter(err: impl Into<anyhow::Error>) -> DashboardError {
+        DashboardError {
+            status: StatusCode::BAD_REQUEST,
+            inner: err.into(),
+        }
+    }
+
+    /// Like [`err`], but for a request that named an entity that doesn't exist, e.g. an unknown
+    /// worker id. Maps to `404 Not Found`.
+    pub fn not_found(err: impl Into<anyhow::Error>) -> DashboardError {
+        DashboardError {
+            status: StatusCode::NOT_FOUND,
+            inner: err.into(),
+        }
+    }
+
+    /// [`MetaError`] already categorizes itself (see [`MetaError::http_status_code`]), so a
+    /// `?`-propagated meta error gets the right status without every call site having to name
+    /// [`err`]/[`invalid_parameter`]/[`not_found`] explicitly.
+    impl From<MetaError> for DashboardError {
+        fn from(err: MetaError) -> Self {
+            DashboardError {
+                status: err.http_status_code(),
+                inner: err.into(),
+            }
+        }
     }
 
     impl IntoResponse for DashboardError {
         fn into_response(self) -> axum::response::Response {
             let mut resp = Json(json!({
-                "error": format!("{}", self.0),
-                "info":  format!("{:?}", self.0),
+                "code": self.status.as_u16(),
+                "message": format!("{}", self.inner),
             }))
             .into_response();
-            *resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            *resp.status_mut() = self.status;
             resp
         }
     }
@@ -99,7 +157,7 @@ pub(super) mod handlers {
             .list_worker_node(
                 WorkerType::from_i32(ty)
                     .ok_or_else(|| anyhow!("invalid worker type"))
-                    .map_err(err)?,
+                    .map_err(invalid_parameter)?,
                 None,
             )
             .await;
@@ -107,6 +165,18 @@ pub(super) mod handlers {
         Ok(result.into())
     }
 
+    pub async fn list_clusters_summary<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<HashMap<String, u64>>> {
+        let node_map = srv.cluster_manager.count_worker_node().await;
+        Ok(Json(
+            node_map
+                .into_iter()
+                .map(|(ty, count)| (format!("{:?}", ty), count))
+                .collect(),
+        ))
+    }
+
     async fn list_table_catalogs_inner<S: MetaStore>(
         meta_store: &S,
         table_type: TableType,
@@ -165,7 +235,13 @@ pub(super) mod handlers {
         Ok(Json(sinks))
     }
 
+    #[derive(serde::Deserialize)]
+    pub struct ListActorsParams {
+        pub node_id: Option<WorkerId>,
+    }
+
     pub async fn list_actors<S: MetaStore>(
+        Query(params): Query<ListActorsParams>,
         Extension(srv): Extension<Service<S>>,
     ) -> Result<Json<Vec<ActorLocation>>> {
         let node_actors = srv.fragment_manager.all_node_actors(true).await;
@@ -175,6 +251,7 @@ pub(super) mod handlers {
             .await;
         let actors = nodes
             .iter()
+            .filter(|node| params.node_id.map_or(true, |node_id| node.id == node_id))
             .map(|node| ActorLocation {
                 node: Some(node.clone()),
                 actors: node_actors.get(&node.id).cloned().unwrap_or_default(),
@@ -184,6 +261,75 @@ pub(super) mod handlers {
         Ok(Json(actors))
     }
 
+    /// Builds the [`handlers::stream_actors`] event stream, polling at `srv.actor_stream_interval`.
+    /// Split out from the handler so tests can pull items off it directly instead of having to
+    /// parse them back out of an SSE response body.
+    pub(crate) fn actor_location_stream<S: MetaStore>(
+        srv: Service<S>,
+        params: ListActorsParams,
+    ) -> impl Stream<Item = Result<Event, std::convert::Infallible>> {
+        IntervalStream::new(tokio::time::interval(srv.actor_stream_interval)).then(move |_| {
+            let srv = srv.clone();
+            async move {
+                let node_actors = srv.fragment_manager.all_node_actors(true).await;
+                let nodes = srv
+                    .cluster_manager
+                    .list_active_streaming_compute_nodes()
+                    .await;
+                let actors = nodes
+                    .iter()
+                    .filter(|node| params.node_id.map_or(true, |node_id| node.id == node_id))
+                    .map(|node| ActorLocation {
+                        node: Some(node.clone()),
+                        actors: node_actors.get(&node.id).cloned().unwrap_or_default(),
+                    })
+                    .collect::<Vec<_>>();
+                Ok(Event::default().json_data(&actors).unwrap())
+            }
+        })
+    }
+
+    /// Continuously push the actor locations as Server-Sent Events, so a dashboard UI can watch
+    /// actors move across compute nodes (e.g. during rescheduling) without polling `/actors`.
+    pub async fn stream_actors<S: MetaStore>(
+        Query(params): Query<ListActorsParams>,
+        Extension(srv): Extension<Service<S>>,
+    ) -> Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>> {
+        Sse::new(actor_location_stream(srv, params)).keep_alive(KeepAlive::default())
+    }
+
+    /// Render the actor dataflow graph (actors as nodes, `upstream_actor_id` links as edges) in
+    /// DOT/Graphviz format so it can be piped straight into `dot -Tsvg` for visualization.
+    pub async fn dump_actor_graph_dot<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<impl IntoResponse> {
+        use std::fmt::Write;
+
+        let node_actors = srv.fragment_manager.all_node_actors(true).await;
+
+        let mut dot = String::new();
+        writeln!(dot, "digraph actors {{").unwrap();
+        for actors in node_actors.values() {
+            for actor in actors {
+                writeln!(
+                    dot,
+                    "  \"{}\" [label=\"actor {} (fragment {})\"];",
+                    actor.actor_id, actor.actor_id, actor.fragment_id
+                )
+                .unwrap();
+                for upstream_actor_id in &actor.upstream_actor_id {
+                    writeln!(dot, "  \"{}\" -> \"{}\";", upstream_actor_id, actor.actor_id).unwrap();
+                }
+            }
+        }
+        writeln!(dot, "}}").unwrap();
+
+        Ok((
+            [(axum::http::header::CONTENT_TYPE, "text/vnd.graphviz")],
+            dot,
+        ))
+    }
+
     pub async fn list_table_fragments<S: MetaStore>(
         Extension(srv): Extension<Service<S>>,
     ) -> Result<Json<Vec<TableActors>>> {
@@ -212,6 +358,67 @@ pub(super) mod handlers {
         Ok(Json(table_fragments))
     }
 
+    /// Render a minimal set of cluster-level gauges (worker node counts, actor counts) in
+    /// Prometheus text exposition format, so the dashboard can be scraped without standing up a
+    /// separate exporter.
+    pub async fn metrics<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<impl IntoResponse> {
+        use std::fmt::Write;
+
+        use risingwave_pb::common::WorkerType;
+
+        let mut text = String::new();
+
+        let worker_counts = srv.cluster_manager.count_worker_node().await;
+        writeln!(
+            text,
+            "# HELP meta_worker_nodes Number of worker nodes registered with meta, by type."
+        )
+        .unwrap();
+        writeln!(text, "# TYPE meta_worker_nodes gauge").unwrap();
+        for ty in [
+            WorkerType::Frontend,
+            WorkerType::ComputeNode,
+            WorkerType::Compactor,
+            WorkerType::RiseCtl,
+            WorkerType::Meta,
+        ] {
+            let count = worker_counts.get(&ty).copied().unwrap_or(0);
+            writeln!(text, "meta_worker_nodes{{type=\"{:?}\"}} {}", ty, count).unwrap();
+        }
+
+        let node_actors = srv.fragment_manager.all_node_actors(true).await;
+        let actor_count: usize = node_actors.values().map(|actors| actors.len()).sum();
+        writeln!(text, "# HELP meta_actors Number of streaming actors scheduled on compute nodes.").unwrap();
+        writeln!(text, "# TYPE meta_actors gauge").unwrap();
+        writeln!(text, "meta_actors {}", actor_count).unwrap();
+
+        Ok((
+            [(axum::http::header::CONTENT_TYPE, "text/plain")],
+            text,
+        ))
+    }
+
+    /// Expose the current hummock version for inspection, e.g. to debug SST/level layout issues
+    /// without going through `risectl`.
+    pub async fn get_hummock_version<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<risingwave_pb::hummock::HummockVersion>> {
+        let version = srv.hummock_manager.get_current_version().await;
+        Ok(Json(version))
+    }
+
+    /// Expose the current compaction-group-to-table assignments and their level configs
+    /// (multipliers, target file sizes), for operators tuning compaction without going through
+    /// `risectl`.
+    pub async fn list_compaction_config<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<Vec<risingwave_pb::hummock::CompactionGroupInfo>>> {
+        let configs = srv.hummock_manager.list_compaction_group().await;
+        Ok(Json(configs))
+    }
+
     pub async fn dump_await_tree<S: MetaStore>(
         Path(worker_id): Path<WorkerId>,
         Extension(srv): Extension<Service<S>>,
@@ -221,7 +428,7 @@ pub(super) mod handlers {
             .get_worker_by_id(worker_id)
             .await
             .context("worker node not found")
-            .map_err(err)?
+            .map_err(not_found)?
             .worker_node;
 
         let client = srv.compute_clients.get(&worker_node).await.map_err(err)?;
@@ -230,23 +437,47 @@ pub(super) mod handlers {
 
         Ok(result.into())
     }
+
+    /// Forces a checkpoint barrier and returns the resulting `max_committed_epoch`, for
+    /// reproducing epoch-progression issues without going through `risectl` or a DML statement.
+    pub async fn flush<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<serde_json::Value>> {
+        let snapshot = srv.barrier_scheduler.flush(true).await?;
+        Ok(Json(json!({
+            "max_committed_epoch": snapshot.committed_epoch,
+        })))
+    }
 }
 
 impl<S> DashboardService<S>
 where
     S: MetaStore,
 {
-    pub async fn serve(self, ui_path: Option<String>) -> Result<()> {
+    /// Serves the dashboard until `shutdown_rx` fires, draining any in-flight requests before the
+    /// listening socket is closed. Returns `Ok(())` only after the server has actually stopped,
+    /// so callers can rely on it for a clean shutdown instead of aborting the task.
+    pub async fn serve(
+        self,
+        ui_path: Option<String>,
+        mut shutdown_rx: tokio::sync::watch::Receiver<()>,
+    ) -> Result<()> {
         use handlers::*;
         let srv = Arc::new(self);
 
+        // Allow any headers too so a UI hosted on a different origin can still issue
+        // preflighted requests (e.g. with a custom `Accept` header) against this API.
         let cors_layer = CorsLayer::new()
             .allow_origin(cors::Any)
-            .allow_methods(vec![Method::GET]);
+            .allow_methods(vec![Method::GET, Method::POST])
+            .allow_headers(cors::Any);
 
         let api_router = Router::new()
             .route("/clusters/:ty", get(list_clusters::<S>))
+            .route("/clusters/summary", get(list_clusters_summary::<S>))
             .route("/actors", get(list_actors::<S>))
+            .route("/actors/stream", get(stream_actors::<S>))
+            .route("/actors/dot", get(dump_actor_graph_dot::<S>))
             .route("/fragments", get(list_table_fragments::<S>))
             .route("/fragments2", get(list_fragments::<S>))
             .route("/materialized_views", get(list_materialized_views::<S>))
@@ -259,7 +490,14 @@ where
                 "/metrics/cluster",
                 get(prometheus::list_prometheus_cluster::<S>),
             )
+            .route("/metrics", get(metrics::<S>))
+            .route("/hummock/version", get(get_hummock_version::<S>))
+            .route(
+                "/hummock/compaction_config",
+                get(list_compaction_config::<S>),
+            )
             .route("/monitor/await_tree/:worker_id", get(dump_await_tree::<S>))
+            .route("/debug/flush", post(flush::<S>))
             .layer(
                 ServiceBuilder::new()
                     .layer(AddExtensionLayer::new(srv.clone()))
@@ -303,8 +541,303 @@ where
 
         axum::Server::bind(&srv.dashboard_addr)
             .serve(app.into_make_service())
+            .with_graceful_shutdown(async move {
+                let _ = shutdown_rx.changed().await;
+            })
             .await
             .map_err(|err| anyhow!(err))?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::response::IntoResponse;
+
+    use super::*;
+    use crate::hummock::test_utils::setup_compute_env;
+    use crate::manager::FragmentManager;
+
+    async fn test_dashboard_service() -> Service<crate::storage::MemStore> {
+        let (srv, _) = test_dashboard_service_with_interval(DEFAULT_ACTOR_STREAM_INTERVAL).await;
+        srv
+    }
+
+    async fn test_dashboard_service_with_interval(
+        actor_stream_interval: Duration,
+    ) -> (
+        Service<crate::storage::MemStore>,
+        crate::barrier::ScheduledBarriers,
+    ) {
+        let (env, hummock_manager, cluster_manager, _) = setup_compute_env(1).await;
+        let fragment_manager = Arc::new(FragmentManager::new(env.clone()).await.unwrap());
+        let (barrier_scheduler, scheduled_barriers) = crate::barrier::BarrierScheduler::new_pair(
+            hummock_manager.clone(),
+            Arc::new(crate::rpc::metrics::MetaMetrics::new()),
+            1,
+        );
+        let srv = Arc::new(DashboardService {
+            dashboard_addr: "127.0.0.1:0".parse().unwrap(),
+            prometheus_endpoint: None,
+            prometheus_client: None,
+            cluster_manager,
+            fragment_manager,
+            hummock_manager,
+            compute_clients: ComputeClientPool::default(),
+            meta_store: env.meta_store_ref(),
+            barrier_scheduler,
+            actor_stream_interval,
+        });
+        (srv, scheduled_barriers)
+    }
+
+    #[tokio::test]
+    async fn test_metrics_handler() {
+        let srv = test_dashboard_service().await;
+        let response = handlers::metrics(Extension(srv)).await.unwrap();
+        let response = response.into_response();
+        assert_eq!(
+            response.headers().get(axum::http::header::CONTENT_TYPE).unwrap(),
+            "text/plain"
+        );
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        // `setup_compute_env` registers one compute node.
+        assert!(body.contains("meta_worker_nodes{type=\"ComputeNode\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn test_get_hummock_version_handler() {
+        let srv = test_dashboard_service().await;
+        let Json(version) = handlers::get_hummock_version(Extension(srv)).await.unwrap();
+        assert_eq!(version.id, risingwave_hummock_sdk::FIRST_VERSION_ID);
+    }
+
+    #[tokio::test]
+    async fn test_list_compaction_config_handler() {
+        let srv = test_dashboard_service().await;
+        let Json(configs) = handlers::list_compaction_config(Extension(srv))
+            .await
+            .unwrap();
+        assert!(!configs.is_empty());
+        let config = configs[0].compaction_config.as_ref().unwrap();
+        assert_eq!(config.max_level, 6);
+        assert_eq!(config.target_file_size_base, 32 * 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_list_clusters_invalid_worker_type_is_bad_request() {
+        let srv = test_dashboard_service().await;
+        let err = handlers::list_clusters(Path(-1), Extension(srv))
+            .await
+            .unwrap_err();
+        let response = err.into_response();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], 400);
+        assert!(body["message"].as_str().unwrap().contains("invalid worker type"));
+    }
+
+    #[tokio::test]
+    async fn test_dump_await_tree_unknown_worker_is_not_found() {
+        let srv = test_dashboard_service().await;
+        let err = handlers::dump_await_tree(Path(12345), Extension(srv))
+            .await
+            .unwrap_err();
+        let response = err.into_response();
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], 404);
+    }
+
+    #[tokio::test]
+    async fn test_stream_actors_uses_configured_interval() {
+        let (srv, _) = test_dashboard_service_with_interval(Duration::from_millis(5)).await;
+        let params = handlers::ListActorsParams { node_id: None };
+        let mut stream = Box::pin(handlers::actor_location_stream(srv, params));
+
+        // With a 5ms configured interval, two events should arrive well within 500ms; this would
+        // time out if the stream still used the old hardcoded 1s interval.
+        tokio::time::timeout(Duration::from_millis(500), async {
+            stream.next().await.unwrap().unwrap();
+            stream.next().await.unwrap().unwrap();
+        })
+        .await
+        .expect("stream_actors should use the configured interval, not a hardcoded one");
+    }
+
+    #[tokio::test]
+    async fn test_list_clusters_summary_handler() {
+        use risingwave_pb::common::{HostAddress, WorkerType};
+        use risingwave_pb::meta::add_worker_node_request::Property as AddNodeProperty;
+
+        let srv = test_dashboard_service().await;
+        for (ty, port) in [(WorkerType::ComputeNode, 5000), (WorkerType::Frontend, 6000)] {
+            let host_address = HostAddress {
+                host: "localhost".to_string(),
+                port,
+            };
+            srv.cluster_manager
+                .add_worker_node(
+                    ty,
+                    host_address,
+                    AddNodeProperty {
+                        worker_node_parallelism: 1,
+                        is_streaming: true,
+                        is_serving: true,
+                        is_unschedulable: false,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        let Json(summary) = handlers::list_clusters_summary(Extension(srv)).await.unwrap();
+        // One compute node from `setup_compute_env`, plus the one added above.
+        assert_eq!(summary.get("ComputeNode"), Some(&2));
+        assert_eq!(summary.get("Frontend"), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn test_list_actors_handler_filters_by_node_id() {
+        use std::collections::BTreeMap;
+
+        use risingwave_common::catalog::TableId;
+        use risingwave_pb::common::{HostAddress, ParallelUnit, WorkerType};
+        use risingwave_pb::meta::add_worker_node_request::Property as AddNodeProperty;
+        use risingwave_pb::meta::table_fragments::Fragment;
+        use risingwave_pb::stream_plan::StreamActor;
+
+        use crate::model::TableFragments;
+
+        let srv = test_dashboard_service().await;
+        let mut node_ids = Vec::new();
+        for port in [5000, 5001] {
+            let host_address = HostAddress {
+                host: "localhost".to_string(),
+                port,
+            };
+            let node = srv
+                .cluster_manager
+                .add_worker_node(
+                    WorkerType::ComputeNode,
+                    host_address.clone(),
+                    AddNodeProperty {
+                        worker_node_parallelism: 1,
+                        is_streaming: true,
+                        is_serving: true,
+                        is_unschedulable: false,
+                    },
+                )
+                .await
+                .unwrap();
+            srv.cluster_manager
+                .activate_worker_node(host_address)
+                .await
+                .unwrap();
+            node_ids.push(node.id);
+        }
+
+        let fragment = Fragment {
+            fragment_id: 0,
+            actors: vec![
+                StreamActor {
+                    actor_id: 1,
+                    fragment_id: 0,
+                    ..Default::default()
+                },
+                StreamActor {
+                    actor_id: 2,
+                    fragment_id: 0,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let actor_locations = BTreeMap::from([
+            (
+                1,
+                ParallelUnit {
+                    id: 0,
+                    worker_node_id: node_ids[0],
+                },
+            ),
+            (
+                2,
+                ParallelUnit {
+                    id: 1,
+                    worker_node_id: node_ids[1],
+                },
+            ),
+        ]);
+        let table_fragments = TableFragments::new(
+            TableId::new(888),
+            BTreeMap::from([(0, fragment)]),
+            &actor_locations,
+            Default::default(),
+        );
+        srv.fragment_manager
+            .start_create_table_fragments(table_fragments)
+            .await
+            .unwrap();
+
+        let Json(all_actors) = handlers::list_actors(
+            Query(handlers::ListActorsParams { node_id: None }),
+            Extension(srv.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            all_actors.iter().map(|l| l.actors.len()).sum::<usize>(),
+            2
+        );
+
+        let Json(filtered) = handlers::list_actors(
+            Query(handlers::ListActorsParams {
+                node_id: Some(node_ids[0]),
+            }),
+            Extension(srv),
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].node.as_ref().unwrap().id, node_ids[0]);
+        assert_eq!(filtered[0].actors.len(), 1);
+        assert_eq!(filtered[0].actors[0].actor_id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_serve_graceful_shutdown() {
+        let srv = test_dashboard_service().await;
+        let service = Arc::try_unwrap(srv).ok().unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(());
+
+        let serve_handle = tokio::spawn(service.serve(None, shutdown_rx));
+        shutdown_tx.send(()).unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), serve_handle)
+            .await
+            .expect("serve did not shut down promptly")
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_flush_handler() {
+        let (srv, scheduled_barriers) =
+            test_dashboard_service_with_interval(DEFAULT_ACTOR_STREAM_INTERVAL).await;
+
+        // No `GlobalBarrierManager` is running in this test, so manually collect the barrier that
+        // `flush` schedules in order to unblock it.
+        let collect_handle = tokio::spawn(async move { scheduled_barriers.collect_one_for_test().await });
+
+        let Json(body) = handlers::flush(Extension(srv)).await.unwrap();
+        collect_handle.await.unwrap();
+
+        body["max_committed_epoch"]
+            .as_u64()
+            .expect("max_committed_epoch should be a numeric field");
+    }
+}
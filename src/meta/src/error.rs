@@ -209,6 +209,19 @@ impl From<MetaError> for tonic::Status {
     }
 }
 
+impl MetaError {
+    /// HTTP status appropriate for surfacing this error from the dashboard's JSON API: a bad
+    /// request is `400`, a missing catalog entry is `404`, and everything else falls back to
+    /// `500`, including the variants that the `tonic::Status` conversion above maps to other codes.
+    pub fn http_status_code(&self) -> axum::http::StatusCode {
+        match &*self.inner {
+            MetaErrorInner::InvalidParameter(_) => axum::http::StatusCode::BAD_REQUEST,
+            MetaErrorInner::CatalogIdNotFound(_, _) => axum::http::StatusCode::NOT_FOUND,
+            _ => axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
 impl From<PbFieldNotFound> for MetaError {
     fn from(e: PbFieldNotFound) -> Self {
         MetadataModelError::from(e).into()
@@ -398,6 +398,12 @@ pub async fn start_service_as_election_leader<S: MetaStore>(
         Some(election_client) => Either::Left(election_client),
     });
 
+    let (barrier_scheduler, scheduled_barriers) = BarrierScheduler::new_pair(
+        hummock_manager.clone(),
+        meta_metrics.clone(),
+        system_params_reader.checkpoint_frequency() as usize,
+    );
+
     #[cfg(not(madsim))]
     if let Some(ref dashboard_addr) = address_info.dashboard_addr {
         let dashboard_service = crate::dashboard::DashboardService {
@@ -409,19 +415,16 @@ pub async fn start_service_as_election_leader<S: MetaStore>(
             }),
             cluster_manager: cluster_manager.clone(),
             fragment_manager: fragment_manager.clone(),
+            hummock_manager: hummock_manager.clone(),
             compute_clients: ComputeClientPool::default(),
             meta_store: env.meta_store_ref(),
+            barrier_scheduler: barrier_scheduler.clone(),
+            actor_stream_interval: crate::dashboard::DEFAULT_ACTOR_STREAM_INTERVAL,
         };
         // TODO: join dashboard service back to local thread.
-        tokio::spawn(dashboard_service.serve(address_info.ui_path));
+        tokio::spawn(dashboard_service.serve(address_info.ui_path, svc_shutdown_rx.clone()));
     }
 
-    let (barrier_scheduler, scheduled_barriers) = BarrierScheduler::new_pair(
-        hummock_manager.clone(),
-        meta_metrics.clone(),
-        system_params_reader.checkpoint_frequency() as usize,
-    );
-
     let source_manager = Arc::new(
         SourceManager::new(
             env.clone(),
@@ -958,6 +958,8 @@ pub enum Statement {
         columns: Vec<Ident>,
         /// A SQL query that specifies what to insert
         source: Box<Query>,
+        /// Upsert clause, e.g. `ON CONFLICT DO NOTHING` / `ON CONFLICT DO UPDATE SET ...`
+        on_conflict: Option<OnConflict>,
         /// Define output of this insert statement
         returning: Vec<SelectItem>,
     },
@@ -1272,6 +1274,7 @@ impl fmt::Display for Statement {
                 table_name,
                 columns,
                 source,
+                on_conflict,
                 returning,
             } => {
                 write!(f, "INSERT INTO {table_name} ", table_name = table_name,)?;
@@ -1279,6 +1282,9 @@ impl fmt::Display for Statement {
                     write!(f, "({}) ", display_comma_separated(columns))?;
                 }
                 write!(f, "{}", source)?;
+                if let Some(on_conflict) = on_conflict {
+                    write!(f, "{}", on_conflict)?;
+                }
                 if !returning.is_empty() {
                     write!(f, " RETURNING ({})", display_comma_separated(returning))?;
                 }
@@ -1745,6 +1751,29 @@ impl fmt::Display for OnInsert {
     }
 }
 
+/// `ON CONFLICT` clause of an `INSERT` statement (PostgreSQL syntax)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OnConflict {
+    /// ON CONFLICT DO NOTHING
+    DoNothing,
+    /// ON CONFLICT DO UPDATE SET ...
+    DoUpdate(Vec<Assignment>),
+}
+
+impl fmt::Display for OnConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DoNothing => write!(f, " ON CONFLICT DO NOTHING"),
+            Self::DoUpdate(assignments) => write!(
+                f,
+                " ON CONFLICT DO UPDATE SET {}",
+                display_comma_separated(assignments)
+            ),
+        }
+    }
+}
+
 /// Privileges granted in a GRANT statement or revoked in a REVOKE statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -135,6 +135,7 @@ define_keywords!(
     COMMITTED,
     CONCURRENTLY,
     CONDITION,
+    CONFLICT,
     CONFLUENT,
     CONNECT,
     CONNECTION,
@@ -326,6 +327,7 @@ define_keywords!(
     NOSCAN,
     NOSUPERUSER,
     NOT,
+    NOTHING,
     NOTNULL,
     NTH_VALUE,
     NTILE,
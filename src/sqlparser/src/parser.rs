@@ -4304,16 +4304,36 @@ impl Parser {
         let columns = self.parse_parenthesized_column_list(Optional)?;
 
         let source = Box::new(self.parse_query()?);
+        let on_conflict = self.parse_on_conflict()?;
         let returning = self.parse_returning(Optional)?;
 
         Ok(Statement::Insert {
             table_name,
             columns,
             source,
+            on_conflict,
             returning,
         })
     }
 
+    /// Parse the optional `ON CONFLICT DO NOTHING` / `ON CONFLICT DO UPDATE SET ...` clause of an
+    /// `INSERT` statement.
+    pub fn parse_on_conflict(&mut self) -> Result<Option<OnConflict>, ParserError> {
+        if !self.parse_keyword(Keyword::ON) {
+            return Ok(None);
+        }
+        self.expect_keyword(Keyword::CONFLICT)?;
+        self.expect_keyword(Keyword::DO)?;
+        if self.parse_keyword(Keyword::NOTHING) {
+            Ok(Some(OnConflict::DoNothing))
+        } else {
+            self.expect_keyword(Keyword::UPDATE)?;
+            self.expect_keyword(Keyword::SET)?;
+            let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+            Ok(Some(OnConflict::DoUpdate(assignments)))
+        }
+    }
+
     pub fn parse_update(&mut self) -> Result<Statement, ParserError> {
         let table_name = self.parse_object_name()?;
 
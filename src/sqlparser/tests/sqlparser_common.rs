@@ -90,6 +90,29 @@ fn parse_insert_values() {
     verified_stmt("INSERT INTO customer WITH foo AS (SELECT 1) SELECT * FROM foo UNION VALUES (1)");
 }
 
+#[test]
+fn parse_insert_on_conflict() {
+    match verified_stmt("INSERT INTO customer VALUES (1) ON CONFLICT DO NOTHING") {
+        Statement::Insert { on_conflict, .. } => {
+            assert_eq!(on_conflict, Some(OnConflict::DoNothing));
+        }
+        _ => unreachable!(),
+    }
+
+    match verified_stmt("INSERT INTO customer VALUES (1) ON CONFLICT DO UPDATE SET id = 2") {
+        Statement::Insert { on_conflict, .. } => {
+            assert_eq!(
+                on_conflict,
+                Some(OnConflict::DoUpdate(vec![Assignment {
+                    id: vec![Ident::new_unchecked("id")],
+                    value: AssignmentValue::Expr(Expr::Value(number("2"))),
+                }]))
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
 #[test]
 fn parse_update() {
     let sql = "UPDATE t SET a = 1, b = 2, c = 3, d = DEFAULT WHERE e";
@@ -134,6 +134,7 @@ fn build_block_data(t: u32, i: u64) -> Bytes {
         capacity: BLOCK_CAPACITY,
         compression_algorithm: CompressionAlgorithm::None,
         restart_interval: RESTART_INTERVAL,
+        adaptive_restart: false,
     };
     let mut builder = BlockBuilder::new(options);
     let mut item_count = 0;
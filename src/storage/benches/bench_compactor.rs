@@ -91,6 +91,9 @@ async fn build_table(
         restart_interval: 16,
         bloom_false_positive: 0.001,
         compression_algorithm: CompressionAlgorithm::None,
+        max_sst_key_count: u64::MAX,
+        build_bloom_filter: true,
+        adaptive_restart: false,
     };
     let writer = sstable_store.create_sst_writer(
         sstable_object_id,
@@ -150,6 +153,19 @@ fn bench_table_build(c: &mut Criterion) {
     });
 }
 
+async fn scan_all_table_batched(info: &SstableInfo, sstable_store: SstableStoreRef) {
+    let mut stats = StoreLocalStatistic::default();
+    let table = sstable_store.sstable(info, &mut stats).await.unwrap();
+    let default_read_options = Arc::new(SstableIteratorReadOptions::default());
+    let mut iter = SstableIterator::new(table, sstable_store.clone(), default_read_options);
+    iter.rewind().await.unwrap();
+    let mut buf = Vec::with_capacity(1024);
+    while iter.is_valid() {
+        buf.clear();
+        iter.advance_within_block(&mut buf, 1024).await.unwrap();
+    }
+}
+
 fn bench_table_scan(c: &mut Criterion) {
     let sstable_store = mock_sstable_store();
     let runtime = tokio::runtime::Builder::new_current_thread()
@@ -171,18 +187,33 @@ fn bench_table_scan(c: &mut Criterion) {
         b.to_async(FuturesExecutor)
             .iter(|| scan_all_table(&info1, sstable_store.clone()));
     });
+    c.bench_function("bench_table_iterator_batched", |b| {
+        let info1 = info.clone();
+        b.to_async(FuturesExecutor)
+            .iter(|| scan_all_table_batched(&info1, sstable_store.clone()));
+    });
 }
 
-async fn compact<I: HummockIterator<Direction = Forward>>(iter: I, sstable_store: SstableStoreRef) {
+async fn compact<I: HummockIterator<Direction = Forward>>(
+    iter: I,
+    sstable_store: SstableStoreRef,
+    pipeline_finish: bool,
+) {
     let opt = SstableBuilderOptions {
         capacity: 32 * 1024 * 1024,
         block_capacity: 64 * 1024,
         restart_interval: 16,
         bloom_false_positive: 0.001,
         compression_algorithm: CompressionAlgorithm::None,
+        max_sst_key_count: u64::MAX,
+        build_bloom_filter: true,
+        adaptive_restart: false,
     };
     let mut builder =
         CapacitySplitTableBuilder::for_test(LocalTableBuilderFactory::new(32, sstable_store, opt));
+    if pipeline_finish {
+        builder = builder.with_concurrent_finish(4);
+    }
 
     let task_config = TaskConfig {
         key_range: KeyRange::inf(),
@@ -227,6 +258,7 @@ fn bench_merge_iterator_compactor(c: &mut Criterion) {
     let read_options = Arc::new(SstableIteratorReadOptions {
         cache_policy: CachePolicy::Fill(CachePriority::High),
         must_iterated_end_user_key: None,
+        ..Default::default()
     });
     c.bench_function("bench_union_merge_iterator", |b| {
         b.to_async(FuturesExecutor).iter(|| {
@@ -236,30 +268,99 @@ fn bench_merge_iterator_compactor(c: &mut Criterion) {
                 ConcatIterator::new(level2.clone(), sstable_store.clone(), read_options.clone()),
             ];
             let iter = UnorderedMergeIteratorInner::for_compactor(sub_iters);
-            async move { compact(iter, sstable_store1).await }
+            async move { compact(iter, sstable_store1, false).await }
         });
     });
-    c.bench_function("bench_merge_iterator", |b| {
+    for (bench_name, pipeline_finish) in
+        [("bench_merge_iterator", false), ("bench_merge_iterator_pipelined", true)]
+    {
+        c.bench_function(bench_name, |b| {
+            b.to_async(&runtime).iter(|| {
+                let sub_iters = vec![
+                    ConcatSstableIterator::new(
+                        vec![0],
+                        level1.clone(),
+                        KeyRange::inf(),
+                        sstable_store.clone(),
+                        Arc::new(TaskProgress::default()),
+                    ),
+                    ConcatSstableIterator::new(
+                        vec![0],
+                        level2.clone(),
+                        KeyRange::inf(),
+                        sstable_store.clone(),
+                        Arc::new(TaskProgress::default()),
+                    ),
+                ];
+                let iter = UnorderedMergeIteratorInner::for_compactor(sub_iters);
+                let sstable_store1 = sstable_store.clone();
+                async move { compact(iter, sstable_store1, pipeline_finish).await }
+            });
+        });
+    }
+}
+
+/// Builds `table_count` tiny, non-overlapping tables so `ConcatSstableIterator::seek` has a
+/// sizeable `sstables` list to binary-search over, the scenario where re-deriving each table's
+/// boundary key on every seek would show up.
+async fn build_many_small_tables(
+    sstable_store: SstableStoreRef,
+    table_count: u64,
+    keys_per_table: u64,
+) -> Vec<SstableInfo> {
+    let mut infos = Vec::with_capacity(table_count as usize);
+    for table_idx in 0..table_count {
+        let start = table_idx * keys_per_table;
+        let info = build_table(
+            sstable_store.clone(),
+            table_idx,
+            start..(start + keys_per_table),
+            1,
+        )
+        .await;
+        infos.push(info);
+    }
+    infos
+}
+
+async fn seek_n_times<F>(mut iter: ConcatSstableIterator, seek_count: u64, seek_key_of: F)
+where
+    F: Fn(u64) -> FullKey<Vec<u8>>,
+{
+    for i in 0..seek_count {
+        let key = seek_key_of(i);
+        iter.seek(key.to_ref()).await.unwrap();
+    }
+}
+
+fn bench_concat_sstable_iterator_seek(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let sstable_store = mock_sstable_store();
+    const TABLE_COUNT: u64 = 2000;
+    const KEYS_PER_TABLE: u64 = 16;
+    const SEEK_COUNT: u64 = 10_000;
+    let infos = runtime.block_on(build_many_small_tables(
+        sstable_store.clone(),
+        TABLE_COUNT,
+        KEYS_PER_TABLE,
+    ));
+    let total_keys = TABLE_COUNT * KEYS_PER_TABLE;
+
+    // The precomputed path: `ConcatSstableIterator::new` builds `left_boundary_keys` once, and
+    // every seek below reuses it instead of re-deriving each candidate table's boundary key from
+    // `SstableInfo::key_range` on the fly.
+    c.bench_function("bench_concat_sstable_iterator_seek_precomputed", |b| {
         b.to_async(&runtime).iter(|| {
-            let sub_iters = vec![
-                ConcatSstableIterator::new(
-                    vec![0],
-                    level1.clone(),
-                    KeyRange::inf(),
-                    sstable_store.clone(),
-                    Arc::new(TaskProgress::default()),
-                ),
-                ConcatSstableIterator::new(
-                    vec![0],
-                    level2.clone(),
-                    KeyRange::inf(),
-                    sstable_store.clone(),
-                    Arc::new(TaskProgress::default()),
-                ),
-            ];
-            let iter = UnorderedMergeIteratorInner::for_compactor(sub_iters);
-            let sstable_store1 = sstable_store.clone();
-            async move { compact(iter, sstable_store1).await }
+            let iter = ConcatSstableIterator::new(
+                vec![0],
+                infos.clone(),
+                KeyRange::inf(),
+                sstable_store.clone(),
+                Arc::new(TaskProgress::default()),
+            );
+            seek_n_times(iter, SEEK_COUNT, |i| test_key_of((i % total_keys) as usize, 1))
         });
     });
 }
@@ -268,6 +369,7 @@ criterion_group!(
     benches,
     bench_table_build,
     bench_table_scan,
-    bench_merge_iterator_compactor
+    bench_merge_iterator_compactor,
+    bench_concat_sstable_iterator_seek
 );
 criterion_main!(benches);
@@ -18,6 +18,7 @@ use bytes::BufMut;
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
+use risingwave_storage::hummock::ZSTD_DEFAULT_COMPRESSION_LEVEL;
 
 const TABLES_PER_SSTABLE: u32 = 10;
 const KEYS_PER_TABLE: u64 = 100;
@@ -67,6 +68,12 @@ fn stream_compression(dataset: Vec<Vec<u8>>) -> Vec<u8> {
     buf
 }
 
+fn block_compression_zstd(data: Vec<u8>) -> Vec<u8> {
+    let mut encoder = zstd::Encoder::new(vec![], ZSTD_DEFAULT_COMPRESSION_LEVEL).unwrap();
+    encoder.write_all(&data).unwrap();
+    encoder.finish().unwrap()
+}
+
 fn bench_compression(c: &mut Criterion) {
     for vsize in [8, 16, 32, 64] {
         let dataset = gen_dataset(vsize);
@@ -90,21 +97,33 @@ fn bench_compression(c: &mut Criterion) {
             |b, dataset| b.iter(|| stream_compression(dataset.clone())),
         );
 
+        c.bench_with_input(
+            BenchmarkId::new(format!("block compression zstd - vsize: {}B", vsize), ""),
+            &data,
+            |b, data| b.iter(|| block_compression_zstd(data.clone())),
+        );
+
         let uncompressed = data.len();
-        let block_compressed = block_compression(data).len();
+        let block_compressed = block_compression(data.clone()).len();
         let stream_compressed = stream_compression(dataset).len();
+        let block_compressed_zstd = block_compression_zstd(data).len();
 
         println!("uncompressed size: {}", uncompressed);
         println!(
-            "block compressed size: {}, rate: {:.3}",
+            "block compressed size (lz4): {}, rate: {:.3}",
             block_compressed,
             block_compressed as f64 / uncompressed as f64
         );
         println!(
-            "stream compressed size: {}, rate: {:.3}",
+            "stream compressed size (lz4): {}, rate: {:.3}",
             stream_compressed,
             stream_compressed as f64 / uncompressed as f64
         );
+        println!(
+            "block compressed size (zstd): {}, rate: {:.3}",
+            block_compressed_zstd,
+            block_compressed_zstd as f64 / uncompressed as f64
+        );
     }
 }
 
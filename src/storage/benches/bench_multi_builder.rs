@@ -90,6 +90,9 @@ fn get_builder_options(capacity_mb: usize) -> SstableBuilderOptions {
         restart_interval: 16,
         bloom_false_positive: 0.001,
         compression_algorithm: CompressionAlgorithm::None,
+        max_sst_key_count: u64::MAX,
+        build_bloom_filter: true,
+        adaptive_restart: false,
     }
 }
 
@@ -12,8 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use bytes::Bytes;
 use risingwave_pb::hummock::{CompactTask, LevelType, SstableInfo};
 
+use crate::key_range::KeyRange;
+
 pub fn compact_task_to_string(compact_task: &CompactTask) -> String {
     use std::fmt::Write;
 
@@ -123,3 +126,112 @@ pub fn estimate_state_for_compaction(task: &CompactTask) -> (u64, usize, u64) {
 
     (total_memory_size, total_file_count, total_key_count)
 }
+
+/// Splits `tables`, assumed sorted and non-overlapping, into at most `n` sub-ranges balanced by
+/// total `file_size`, so each sub-range can drive its own `ConcatSstableIterator` and let a
+/// compaction task be parallelized. Split boundaries always land on a table edge nearest each
+/// `1/n` cumulative-size mark; a single sstable's key range is never cut in half. When `tables`
+/// has fewer edges than `n - 1`, fewer than `n` ranges are returned.
+pub fn split_key_ranges(tables: &[SstableInfo], n: usize) -> Vec<KeyRange> {
+    if tables.is_empty() || n <= 1 {
+        return vec![KeyRange::inf()];
+    }
+
+    let mut cumulative = Vec::with_capacity(tables.len());
+    let mut running = 0;
+    for table in tables {
+        running += table.file_size;
+        cumulative.push(running);
+    }
+    let total_size = running;
+
+    let mut boundary_idxs = vec![];
+    let mut last_idx = None;
+    for target_num in 1..n {
+        let target = total_size * target_num as u64 / n as u64;
+        let start = last_idx.map_or(0, |idx| idx + 1);
+        let mut best: Option<(usize, u64)> = None;
+        for idx in start..tables.len() - 1 {
+            let diff = cumulative[idx].abs_diff(target);
+            if best.map_or(true, |(_, best_diff)| diff < best_diff) {
+                best = Some((idx, diff));
+            }
+            if cumulative[idx] > target {
+                break;
+            }
+        }
+        if let Some((idx, _)) = best {
+            boundary_idxs.push(idx);
+            last_idx = Some(idx);
+        }
+    }
+
+    let mut ranges = Vec::with_capacity(boundary_idxs.len() + 1);
+    let mut left = Bytes::new();
+    for idx in boundary_idxs {
+        let right = Bytes::copy_from_slice(&tables[idx].key_range.as_ref().unwrap().right);
+        ranges.push(KeyRange::new(left, right.clone()));
+        left = right;
+    }
+    ranges.push(KeyRange::new(left, Bytes::new()));
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::hummock::KeyRange as PbKeyRange;
+
+    use super::*;
+
+    fn table(file_size: u64, left: u8, right: u8) -> SstableInfo {
+        SstableInfo {
+            key_range: Some(PbKeyRange {
+                left: vec![left],
+                right: vec![right],
+                right_exclusive: false,
+            }),
+            file_size,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_split_key_ranges_balances_by_size() {
+        let tables = vec![
+            table(10, 0, 1),
+            table(10, 1, 2),
+            table(10, 2, 3),
+            table(10, 3, 4),
+            table(60, 4, 5),
+        ];
+
+        let ranges = split_key_ranges(&tables, 2);
+        assert_eq!(ranges.len(), 2);
+        assert!(ranges[0].left.is_empty());
+        assert!(ranges[1].right.is_empty());
+        assert_eq!(ranges[0].right, ranges[1].left);
+
+        // Total size is 100; the edge after the 4th table (cumulative 40) is closer to the
+        // halfway mark (50) than the edge after the 5th table, which doesn't exist since there's
+        // nothing left to split off after the last table.
+        assert_eq!(ranges[0].right, Bytes::from(vec![4]));
+    }
+
+    #[test]
+    fn test_split_key_ranges_partitions_the_space() {
+        let tables = vec![table(5, 0, 1), table(5, 1, 2), table(5, 2, 3)];
+        let ranges = split_key_ranges(&tables, 4);
+        // Only two internal edges exist, so at most 3 ranges can be produced even though 4 were
+        // requested.
+        assert!(ranges.len() <= 3);
+        for (prev, next) in ranges.iter().zip(ranges.iter().skip(1)) {
+            assert_eq!(prev.right, next.left);
+        }
+    }
+
+    #[test]
+    fn test_split_key_ranges_single_table() {
+        let tables = vec![table(10, 0, 1)];
+        assert_eq!(split_key_ranges(&tables, 4), vec![KeyRange::inf()]);
+    }
+}
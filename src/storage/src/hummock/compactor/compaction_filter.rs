@@ -102,6 +102,41 @@ impl TtlCompactionFilter {
     }
 }
 
+/// Drops keys whose epoch is older than the retention watermark configured for their user-key
+/// prefix, e.g. to implement per-partition TTL that isn't expressible as a single table-level
+/// retention window.
+#[derive(Clone)]
+pub struct WatermarkCompactionFilter {
+    /// `(user_key_prefix, watermark_epoch)`, sorted by descending prefix length so that the most
+    /// specific matching prefix is checked first.
+    prefix_to_watermark: Vec<(Vec<u8>, u64)>,
+}
+
+impl WatermarkCompactionFilter {
+    pub fn new(prefix_to_watermark: HashMap<Vec<u8>, u64>) -> Self {
+        let mut prefix_to_watermark = prefix_to_watermark.into_iter().collect::<Vec<_>>();
+        prefix_to_watermark.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        Self { prefix_to_watermark }
+    }
+}
+
+impl CompactionFilter for WatermarkCompactionFilter {
+    fn should_delete(&mut self, key: FullKey<&[u8]>) -> bool {
+        let user_key = key.user_key.table_key.as_ref();
+        // `prefix_to_watermark` is sorted longest-prefix-first, so the first match here is
+        // always the most specific one, per the "most specific prefix wins" contract. A cache of
+        // the last matched prefix was tried here before, but it's unsound: it would short-circuit
+        // to a shorter, previously-matched prefix even when a longer, more specific prefix also
+        // matches the current key.
+        for (prefix, watermark) in &self.prefix_to_watermark {
+            if user_key.starts_with(prefix) {
+                return key.epoch < *watermark;
+            }
+        }
+        false
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct MultiCompactionFilter {
     filter_vec: Vec<Box<dyn CompactionFilter>>,
@@ -128,11 +163,45 @@ mod tests {
     use risingwave_common::catalog::TableId;
     use risingwave_hummock_sdk::key::{FullKey, TableKey};
 
-    use super::{CompactionFilter, TtlCompactionFilter};
+    use super::{CompactionFilter, TtlCompactionFilter, WatermarkCompactionFilter};
 
     #[test]
     fn test_ttl_u32() {
         let mut ttl_filter = TtlCompactionFilter::new(HashMap::from_iter([(1, 4000000000)]), 1);
         ttl_filter.should_delete(FullKey::new(TableId::new(1), TableKey(vec![]), 1).to_ref());
     }
+
+    #[test]
+    fn test_watermark_compaction_filter() {
+        let table_id = TableId::new(1);
+        let old_key = FullKey::new(table_id, TableKey(b"key1".to_vec()), 1);
+        let new_key = FullKey::new(table_id, TableKey(b"key1".to_vec()), 2);
+
+        let mut watermark_filter =
+            WatermarkCompactionFilter::new(HashMap::from_iter([(b"key1".to_vec(), 2)]));
+        assert!(watermark_filter.should_delete(old_key.to_ref()));
+        assert!(!watermark_filter.should_delete(new_key.to_ref()));
+
+        // Keys that don't match any configured prefix are kept regardless of epoch.
+        let unrelated_key = FullKey::new(table_id, TableKey(b"other".to_vec()), 1);
+        assert!(!watermark_filter.should_delete(unrelated_key.to_ref()));
+    }
+
+    #[test]
+    fn test_watermark_compaction_filter_most_specific_prefix_wins() {
+        let table_id = TableId::new(1);
+        let mut watermark_filter = WatermarkCompactionFilter::new(HashMap::from_iter([
+            (b"ab".to_vec(), 10),
+            (b"abc".to_vec(), 5),
+        ]));
+
+        // "ab1" only matches the "ab" prefix, watermark 10.
+        let ab_key = FullKey::new(table_id, TableKey(b"ab1".to_vec()), 7);
+        assert!(watermark_filter.should_delete(ab_key.to_ref()));
+
+        // "abc1" matches both "ab" and the more specific "abc"; the "abc" watermark (5) must win
+        // even though the previous call matched the shorter "ab" prefix.
+        let abc_key = FullKey::new(table_id, TableKey(b"abc1".to_vec()), 7);
+        assert!(!watermark_filter.should_delete(abc_key.to_ref()));
+    }
 }
@@ -32,7 +32,7 @@ use crate::hummock::iterator::{Forward, HummockIterator, UnorderedMergeIteratorI
 use crate::hummock::sstable::CompactionDeleteRangesBuilder;
 use crate::hummock::{
     CachePolicy, CompactionDeleteRanges, CompressionAlgorithm, HummockResult,
-    SstableBuilderOptions, SstableStoreRef,
+    SstableBuilderOptions, SstableStoreRef, ZSTD_DEFAULT_COMPRESSION_LEVEL,
 };
 use crate::monitor::StoreLocalStatistic;
 
@@ -50,7 +50,9 @@ impl CompactorRunner {
         options.compression_algorithm = match task.compression_algorithm {
             0 => CompressionAlgorithm::None,
             1 => CompressionAlgorithm::Lz4,
-            _ => CompressionAlgorithm::Zstd,
+            _ => CompressionAlgorithm::Zstd {
+                level: ZSTD_DEFAULT_COMPRESSION_LEVEL,
+            },
         };
         options.capacity = estimate_task_memory_capacity(context.clone(), &task);
 
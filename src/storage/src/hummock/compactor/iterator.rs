@@ -28,15 +28,74 @@ use risingwave_pb::hummock::SstableInfo;
 
 use crate::hummock::compactor::task_progress::TaskProgress;
 use crate::hummock::iterator::{Forward, HummockIterator};
-use crate::hummock::sstable_store::{BlockStream, SstableStoreRef};
+use crate::hummock::sstable_store::{BlockStream, CachePolicy, SstableStoreRef, TableHolder};
+use crate::hummock::utils::{retry_sstable_load, SstableLoadRetryOptions};
 use crate::hummock::value::HummockValue;
 use crate::hummock::{Block, BlockHolder, BlockIterator, HummockResult};
 use crate::monitor::StoreLocalStatistic;
 
+/// A byte budget shared (via `Arc`) across every [`ConcatSstableIterator`] feeding a single
+/// compaction task. Each concat iterator calls [`Self::try_reserve`] before eagerly streaming a
+/// whole table; once the ceiling is exhausted, further tables fall back to loading blocks one at
+/// a time on demand instead, bounding how much gets prefetched across the whole task at once.
+pub struct PrefetchBudget {
+    ceiling: u64,
+    used: AtomicU64,
+}
+
+impl PrefetchBudget {
+    pub fn new(ceiling: u64) -> Self {
+        Self {
+            ceiling,
+            used: AtomicU64::new(0),
+        }
+    }
+
+    /// Attempts to reserve `bytes` against the shared ceiling. Returns `true` and atomically
+    /// accounts for the reservation iff there is enough budget left; otherwise returns `false`
+    /// without reserving anything.
+    pub fn try_reserve(&self, bytes: u64) -> bool {
+        let mut current = self.used.load(atomic::Ordering::Relaxed);
+        loop {
+            if current.saturating_add(bytes) > self.ceiling {
+                return false;
+            }
+            match self.used.compare_exchange_weak(
+                current,
+                current + bytes,
+                atomic::Ordering::Relaxed,
+                atomic::Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(observed) => current = observed,
+            }
+        }
+    }
+
+    pub fn used(&self) -> u64 {
+        self.used.load(atomic::Ordering::Relaxed)
+    }
+}
+
+/// Where an [`SstableStreamIterator`] gets its blocks from.
+enum BlockSource {
+    /// Eagerly downloads blocks via a single streaming read, started as soon as the table is
+    /// opened. Used when prefetching is unrestricted or budget allowed it.
+    Stream(BlockStream),
+    /// Loads one block at a time on demand via [`crate::hummock::SstableStore::get`], issuing the
+    /// next block's fetch only once the current one is exhausted. Used as the fallback once a
+    /// [`PrefetchBudget`] is exhausted, trading latency for a bounded prefetch footprint.
+    OnDemand {
+        sstable: TableHolder,
+        sstable_store: SstableStoreRef,
+        next_index: usize,
+    },
+}
+
 /// Iterates over the KV-pairs of an SST while downloading it.
 struct SstableStreamIterator {
-    /// The downloading stream.
-    block_stream: BlockStream,
+    /// Where blocks are fetched from: an eager stream, or loaded on demand.
+    block_source: BlockSource,
 
     /// Iterates over the KV-pairs of the current block.
     block_iter: Option<BlockIterator>,
@@ -55,7 +114,7 @@ struct SstableStreamIterator {
 
 impl SstableStreamIterator {
     // We have to handle two internal iterators.
-    //   `block_stream`: iterates over the blocks of the table.
+    //   `block_source`: iterates over the blocks of the table.
     //     `block_iter`: iterates over the KV-pairs of the current block.
     // These iterators work in different ways.
 
@@ -77,7 +136,35 @@ impl SstableStreamIterator {
         task_progress: Arc<TaskProgress>,
     ) -> Self {
         Self {
-            block_stream,
+            block_source: BlockSource::Stream(block_stream),
+            block_iter: None,
+            remaining_blocks: max_block_count,
+            stats_ptr: stats.remote_io_time.clone(),
+            existing_table_ids,
+            sstable_info: sstable_info.clone(),
+            task_progress,
+        }
+    }
+
+    /// Initialises a new [`SstableStreamIterator`] which loads blocks one at a time on demand,
+    /// starting from `start_index`, rather than eagerly streaming them. The iterator reads at
+    /// most `max_block_count` blocks.
+    pub fn new_on_demand(
+        sstable_info: &SstableInfo,
+        existing_table_ids: HashSet<StateTableId>,
+        sstable: TableHolder,
+        sstable_store: SstableStoreRef,
+        start_index: usize,
+        max_block_count: usize,
+        stats: &StoreLocalStatistic,
+        task_progress: Arc<TaskProgress>,
+    ) -> Self {
+        Self {
+            block_source: BlockSource::OnDemand {
+                sstable,
+                sstable_store,
+                next_index: start_index,
+            },
             block_iter: None,
             remaining_blocks: max_block_count,
             stats_ptr: stats.remote_io_time.clone(),
@@ -146,15 +233,39 @@ impl SstableStreamIterator {
         Ok(())
     }
 
-    /// Wrapper function for `self.block_stream.next()` which allows us to measure the time needed.
+    /// Fetches the next block from whichever [`BlockSource`] backs this iterator, allowing us to
+    /// measure the time needed regardless of source.
     async fn download_next_block(&mut self) -> HummockResult<Option<Box<Block>>> {
         let now = Instant::now();
-        let result = self.block_stream.next().await;
+        let result = match &mut self.block_source {
+            BlockSource::Stream(block_stream) => block_stream.next().await?,
+            BlockSource::OnDemand {
+                sstable,
+                sstable_store,
+                next_index,
+            } => {
+                if *next_index >= sstable.value().block_count() {
+                    None
+                } else {
+                    let mut dummy_stats = StoreLocalStatistic::default();
+                    let block = sstable_store
+                        .get(
+                            sstable.value(),
+                            *next_index,
+                            CachePolicy::default(),
+                            &mut dummy_stats,
+                        )
+                        .await?;
+                    *next_index += 1;
+                    Some(Box::new((*block).clone()))
+                }
+            }
+        };
         let add = (now.elapsed().as_secs_f64() * 1000.0).ceil();
         self.stats_ptr
             .fetch_add(add as u64, atomic::Ordering::Relaxed);
 
-        result
+        Ok(result)
     }
 
     /// Moves to the next KV-pair in the table. Assumes that the current position is valid. Even if
@@ -239,6 +350,39 @@ pub struct ConcatSstableIterator {
 
     stats: StoreLocalStatistic,
     task_progress: Arc<TaskProgress>,
+
+    /// When `true`, rolling over from one table to the next collapses a user key that happens to
+    /// straddle the boundary into a single version, keeping only the newer (first-encountered)
+    /// epoch. Off by default since non-overlapping levels don't normally need it.
+    dedup_table_boundary_user_key: bool,
+    /// The user key of the last KV-pair emitted before rolling over to the next table, used by
+    /// [`Self::seek_idx`] to skip a duplicate leading run in that table when
+    /// `dedup_table_boundary_user_key` is set. Recorded on every forward advance and cleared by
+    /// an explicit `seek`/`rewind`, so it never causes a spurious skip after a jump.
+    table_boundary_user_key: Option<Vec<u8>>,
+
+    /// When set, a shared ceiling on how many bytes this iterator (and any sibling
+    /// `ConcatSstableIterator`s that share the same budget) may eagerly prefetch via
+    /// [`SstableStoreRef::get_stream`]. `None` preserves the default behaviour of always
+    /// prefetching eagerly. Once the budget is exhausted, tables are loaded one block at a time
+    /// on demand instead.
+    prefetch_budget: Option<Arc<PrefetchBudget>>,
+
+    /// When set, only keys whose epoch falls within the inclusive `[lo, hi]` window are
+    /// yielded, e.g. to extract an incremental backup delta from a level. Whole tables whose
+    /// `min_epoch`/`max_epoch` metadata proves no overlap with the window are skipped without
+    /// being loaded; keys within an overlapping table are filtered one at a time.
+    epoch_range: Option<(u64, u64)>,
+
+    /// Bounded retry-with-backoff applied to each table load in [`Self::seek_idx`]. Defaults to
+    /// no retries, preserving the original fail-fast behaviour.
+    load_retry_options: SstableLoadRetryOptions,
+
+    /// The encoded left-boundary key of each table in `sstables`, aligned by index and
+    /// precomputed once in [`Self::new`]. [`Self::next_table_exceeds_upper_bound`] consults this
+    /// on every advance instead of re-deriving it from `SstableInfo::key_range` each time, which
+    /// matters at the seek rates a compaction with many small tables can hit.
+    left_boundary_keys: Vec<Vec<u8>>,
 }
 
 impl ConcatSstableIterator {
@@ -252,6 +396,10 @@ impl ConcatSstableIterator {
         sstable_store: SstableStoreRef,
         task_progress: Arc<TaskProgress>,
     ) -> Self {
+        let left_boundary_keys = sst_infos
+            .iter()
+            .map(|table| table.key_range.as_ref().unwrap().left.clone())
+            .collect();
         Self {
             key_range,
             sstable_iter: None,
@@ -261,9 +409,108 @@ impl ConcatSstableIterator {
             sstable_store,
             task_progress,
             stats: StoreLocalStatistic::default(),
+            dedup_table_boundary_user_key: false,
+            table_boundary_user_key: None,
+            prefetch_budget: None,
+            epoch_range: None,
+            load_retry_options: SstableLoadRetryOptions::default(),
+            left_boundary_keys,
         }
     }
 
+    /// Enables collapsing a user key that straddles a table boundary into a single, newest-epoch
+    /// version (see `dedup_table_boundary_user_key`).
+    pub fn with_dedup_table_boundary_user_key(mut self, dedup: bool) -> Self {
+        self.dedup_table_boundary_user_key = dedup;
+        self
+    }
+
+    /// Shares `budget` across this iterator's table prefetches. See `prefetch_budget`.
+    pub fn with_prefetch_budget(mut self, budget: Arc<PrefetchBudget>) -> Self {
+        self.prefetch_budget = Some(budget);
+        self
+    }
+
+    /// Restricts iteration to keys whose epoch lies within the inclusive `[lo, hi]` window. See
+    /// `epoch_range`.
+    pub fn with_epoch_range(mut self, lo: u64, hi: u64) -> Self {
+        self.epoch_range = Some((lo, hi));
+        self
+    }
+
+    /// Configures bounded retry-with-backoff for table loads. See `load_retry_options`.
+    pub fn with_load_retry_options(mut self, options: SstableLoadRetryOptions) -> Self {
+        self.load_retry_options = options;
+        self
+    }
+
+    /// Estimates how many entries fall in the inclusive key range `[lo, hi]`, without loading any
+    /// table's blocks, by summing each overlapping table's `total_key_count` from its
+    /// [`SstableInfo`]. A table fully contained in `[lo, hi]` contributes its whole count; a table
+    /// straddling either boundary contributes half its count, under the assumption that keys are
+    /// roughly evenly distributed within a table. This is a coarse, load-free estimate meant for a
+    /// query planner that wants a ballpark row count before committing to a real scan, not an
+    /// exact count.
+    pub fn estimate_range_rows(&self, lo: FullKey<&[u8]>, hi: FullKey<&[u8]>) -> usize {
+        let mut estimate = 0u64;
+        for table in &self.sstables {
+            let key_range = table.key_range.as_ref().unwrap();
+            let table_left = FullKey::decode(&key_range.left);
+            let table_right = FullKey::decode(&key_range.right);
+            if table_right.cmp(&lo) == Ordering::Less || table_left.cmp(&hi) == Ordering::Greater
+            {
+                // `[lo, hi]` does not overlap this table at all.
+                continue;
+            }
+            let fully_contained = lo.cmp(&table_left) != Ordering::Greater
+                && hi.cmp(&table_right) != Ordering::Less;
+            if fully_contained {
+                estimate += table.total_key_count;
+            } else {
+                estimate += table.total_key_count / 2;
+            }
+        }
+        estimate as usize
+    }
+
+    /// Like [`Self::new`], but only includes as many leading `sst_infos` as fit within
+    /// `max_input_bytes` (summed over [`SstableInfo::file_size`]). At least one table is always
+    /// included, even if it alone exceeds the budget, so the iterator always makes progress.
+    /// Returns the iterator together with the tables after that prefix, which are deferred to a
+    /// later compaction task.
+    pub fn new_bounded(
+        existing_table_ids: Vec<StateTableId>,
+        sst_infos: Vec<SstableInfo>,
+        key_range: KeyRange,
+        sstable_store: SstableStoreRef,
+        task_progress: Arc<TaskProgress>,
+        max_input_bytes: u64,
+    ) -> (Self, Vec<SstableInfo>) {
+        let mut included = Vec::with_capacity(sst_infos.len());
+        let mut deferred = Vec::new();
+        let mut total_bytes = 0u64;
+        for sst_info in sst_infos {
+            if !deferred.is_empty()
+                || (!included.is_empty() && total_bytes + sst_info.file_size > max_input_bytes)
+            {
+                deferred.push(sst_info);
+                continue;
+            }
+            total_bytes += sst_info.file_size;
+            included.push(sst_info);
+        }
+        (
+            Self::new(
+                existing_table_ids,
+                included,
+                key_range,
+                sstable_store,
+                task_progress,
+            ),
+            deferred,
+        )
+    }
+
     #[cfg(test)]
     pub fn for_test(
         existing_table_ids: Vec<StateTableId>,
@@ -303,17 +550,19 @@ impl ConcatSstableIterator {
             let mut found = table_info
                 .table_ids
                 .iter()
-                .any(|table_id| self.existing_table_ids.contains(table_id));
+                .any(|table_id| self.existing_table_ids.contains(table_id))
+                && self.table_overlaps_epoch_range(table_info);
             if !found {
                 self.cur_idx += 1;
                 seek_key = None;
                 continue;
             }
-            let sstable = self
-                .sstable_store
-                .sstable(table_info, &mut self.stats)
-                .verbose_instrument_await("stream_iter_sstable")
-                .await?;
+            let sstable = retry_sstable_load(&self.load_retry_options, || {
+                self.sstable_store
+                    .sstable(table_info, &mut self.stats)
+                    .verbose_instrument_await("stream_iter_sstable")
+            })
+            .await?;
             let stats_ptr = self.stats.remote_io_time.clone();
             let now = Instant::now();
             let block_metas = &sstable.value().meta.block_metas;
@@ -356,26 +605,56 @@ impl ConcatSstableIterator {
                 self.task_progress
                     .num_pending_read_io
                     .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                let block_stream = self
-                    .sstable_store
-                    .get_stream(sstable.value(), Some(start_index))
-                    .verbose_instrument_await("stream_iter_get_stream")
-                    .await?;
-
-                // Determine time needed to open stream.
-                let add = (now.elapsed().as_secs_f64() * 1000.0).ceil();
-                stats_ptr.fetch_add(add as u64, atomic::Ordering::Relaxed);
-
-                let mut sstable_iter = SstableStreamIterator::new(
-                    table_info,
-                    self.existing_table_ids.clone(),
-                    block_stream,
-                    end_index - start_index,
-                    &self.stats,
-                    self.task_progress.clone(),
-                );
+
+                // Eagerly prefetch the table's blocks via a stream, unless a shared budget is
+                // configured and exhausted, in which case we fall back to loading blocks one at a
+                // time on demand to keep the total amount of in-flight prefetched data bounded.
+                let use_on_demand = match &self.prefetch_budget {
+                    None => false,
+                    Some(budget) => !budget.try_reserve(table_info.file_size),
+                };
+
+                let mut sstable_iter = if use_on_demand {
+                    SstableStreamIterator::new_on_demand(
+                        table_info,
+                        self.existing_table_ids.clone(),
+                        sstable.clone(),
+                        self.sstable_store.clone(),
+                        start_index,
+                        end_index - start_index,
+                        &self.stats,
+                        self.task_progress.clone(),
+                    )
+                } else {
+                    let block_stream = self
+                        .sstable_store
+                        .get_stream(sstable.value(), Some(start_index))
+                        .verbose_instrument_await("stream_iter_get_stream")
+                        .await?;
+
+                    // Determine time needed to open stream.
+                    let add = (now.elapsed().as_secs_f64() * 1000.0).ceil();
+                    stats_ptr.fetch_add(add as u64, atomic::Ordering::Relaxed);
+
+                    SstableStreamIterator::new(
+                        table_info,
+                        self.existing_table_ids.clone(),
+                        block_stream,
+                        end_index - start_index,
+                        &self.stats,
+                        self.task_progress.clone(),
+                    )
+                };
                 sstable_iter.seek(seek_key).await?;
 
+                if let Some(boundary_user_key) = self.table_boundary_user_key.take() {
+                    while sstable_iter.is_valid()
+                        && sstable_iter.key().user_key.encode() == boundary_user_key
+                    {
+                        sstable_iter.next().await?;
+                    }
+                }
+
                 if sstable_iter.is_valid() {
                     self.sstable_iter = Some(sstable_iter);
                 } else {
@@ -391,6 +670,90 @@ impl ConcatSstableIterator {
         }
         Ok(())
     }
+
+    /// Returns `true` iff `key` lies beyond `self.key_range`'s upper bound, meaning the caller
+    /// should treat the iterator as exhausted even though the underlying table iterator may still
+    /// report more KV-pairs.
+    fn exceeds_upper_bound(&self, key: FullKey<&[u8]>) -> bool {
+        if self.key_range.right.is_empty() {
+            return false;
+        }
+        match key.cmp(&FullKey::decode(&self.key_range.right)) {
+            Ordering::Less => false,
+            Ordering::Equal => self.key_range.right_exclusive,
+            Ordering::Greater => true,
+        }
+    }
+
+    /// Returns `true` iff the table following `self.cur_idx` (if any) starts entirely beyond
+    /// `self.key_range`'s upper bound, so rolling over to it would immediately be out of range.
+    fn next_table_exceeds_upper_bound(&self) -> bool {
+        if self.key_range.right.is_empty() {
+            return false;
+        }
+        match self.left_boundary_keys.get(self.cur_idx + 1) {
+            None => false,
+            Some(smallest_key) => self.exceeds_upper_bound(FullKey::decode(smallest_key)),
+        }
+    }
+
+    /// Returns `true` iff `table_info`'s `min_epoch`/`max_epoch` metadata overlaps
+    /// `self.epoch_range`, or no epoch range was configured. Lets [`Self::seek_idx`] skip whole
+    /// tables that provably contain no key in range without loading them.
+    fn table_overlaps_epoch_range(&self, table_info: &SstableInfo) -> bool {
+        match self.epoch_range {
+            None => true,
+            Some((lo, hi)) => table_info.min_epoch <= hi && table_info.max_epoch >= lo,
+        }
+    }
+
+    /// Returns `true` iff `key`'s epoch lies within `self.epoch_range`, or no epoch range was
+    /// configured.
+    fn key_epoch_in_range(&self, key: FullKey<&[u8]>) -> bool {
+        match self.epoch_range {
+            None => true,
+            Some((lo, hi)) => key.epoch >= lo && key.epoch <= hi,
+        }
+    }
+
+    /// Advances past the current position exactly once, rolling over to the next table if
+    /// needed, without applying any epoch filtering. The shared core of [`Self::next`]'s
+    /// plain-advance step.
+    async fn advance_once(&mut self) -> HummockResult<()> {
+        let sstable_iter = self.sstable_iter.as_mut().expect("no table iter");
+        if self.dedup_table_boundary_user_key {
+            self.table_boundary_user_key = Some(sstable_iter.key().user_key.encode());
+        }
+
+        sstable_iter.next().await?;
+        if sstable_iter.is_valid() {
+            Ok(())
+        } else if self.next_table_exceeds_upper_bound() {
+            // The remaining tables start beyond `key_range.right`, so there is nothing left
+            // to read. Drop the exhausted table iterator rather than loading the next table
+            // just to find it immediately out of range.
+            self.sstable_iter = None;
+            self.table_boundary_user_key = None;
+            Ok(())
+        } else {
+            // No, seek to next table.
+            self.seek_idx(self.cur_idx + 1, None).await?;
+            Ok(())
+        }
+    }
+
+    /// Skips forward, starting from the current position, past any keys whose epoch falls
+    /// outside `self.epoch_range`, stopping as soon as a key in range is found or the iterator
+    /// becomes invalid.
+    async fn skip_to_epoch_range(&mut self) -> HummockResult<()> {
+        if self.epoch_range.is_none() {
+            return Ok(());
+        }
+        while self.is_valid() && !self.key_epoch_in_range(self.key()) {
+            self.advance_once().await?;
+        }
+        Ok(())
+    }
 }
 
 impl HummockIterator for ConcatSstableIterator {
@@ -402,17 +765,8 @@ impl HummockIterator for ConcatSstableIterator {
 
     fn next(&mut self) -> Self::NextFuture<'_> {
         async {
-            let sstable_iter = self.sstable_iter.as_mut().expect("no table iter");
-
-            // Does just calling `next()` suffice?
-            sstable_iter.next().await?;
-            if sstable_iter.is_valid() {
-                Ok(())
-            } else {
-                // No, seek to next table.
-                self.seek_idx(self.cur_idx + 1, None).await?;
-                Ok(())
-            }
+            self.advance_once().await?;
+            self.skip_to_epoch_range().await
         }
     }
 
@@ -425,16 +779,25 @@ impl HummockIterator for ConcatSstableIterator {
     }
 
     fn is_valid(&self) -> bool {
-        self.sstable_iter.as_ref().map_or(false, |i| i.is_valid())
+        self.sstable_iter
+            .as_ref()
+            .map_or(false, |i| i.is_valid() && !self.exceeds_upper_bound(i.key()))
     }
 
     fn rewind(&mut self) -> Self::RewindFuture<'_> {
-        async { self.seek_idx(0, None).await }
+        async {
+            // An explicit rewind has no boundary-continuation context to dedup against.
+            self.table_boundary_user_key = None;
+            self.seek_idx(0, None).await?;
+            self.skip_to_epoch_range().await
+        }
     }
 
     /// Resets the iterator and seeks to the first position where the stored key >= `key`.
     fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
         async move {
+            // An explicit seek has no boundary-continuation context to dedup against.
+            self.table_boundary_user_key = None;
             let seek_key = if self.key_range.left.is_empty() {
                 key
             } else {
@@ -454,7 +817,8 @@ impl HummockIterator for ConcatSstableIterator {
                 FullKey::decode(max_sst_key).cmp(&seek_key) == Ordering::Less
             });
 
-            self.seek_idx(table_idx, Some(key)).await
+            self.seek_idx(table_idx, Some(key)).await?;
+            self.skip_to_epoch_range().await
         }
     }
 
@@ -466,7 +830,10 @@ impl HummockIterator for ConcatSstableIterator {
 #[cfg(test)]
 mod tests {
     use std::cmp::Ordering;
+    use std::sync::Arc;
 
+    use itertools::Itertools;
+    use risingwave_common::catalog::TableId;
     use risingwave_hummock_sdk::key::{next_full_key, prev_full_key, FullKey};
     use risingwave_hummock_sdk::key_range::KeyRange;
 
@@ -474,8 +841,8 @@ mod tests {
     use crate::hummock::iterator::test_utils::mock_sstable_store;
     use crate::hummock::iterator::HummockIterator;
     use crate::hummock::test_utils::{
-        default_builder_opt_for_test, gen_test_sstable_and_info, test_key_of, test_value_of,
-        TEST_KEYS_COUNT,
+        default_builder_opt_for_test, gen_test_sstable_and_info, test_key_of, test_user_key_of,
+        test_value_of, TEST_KEYS_COUNT,
     };
     use crate::hummock::value::HummockValue;
 
@@ -599,6 +966,82 @@ mod tests {
         assert!(iter.is_valid() && iter.cur_idx == 0 && iter.key() == FullKey::decode(&kr.left));
     }
 
+    #[tokio::test]
+    async fn test_concat_iterator_stops_at_mid_table_upper_bound() {
+        let sstable_store = mock_sstable_store();
+        let mut table_infos = vec![];
+        for object_id in 0..3 {
+            let start_index = object_id * TEST_KEYS_COUNT;
+            let end_index = (object_id + 1) * TEST_KEYS_COUNT;
+            let (_table, table_info) = gen_test_sstable_and_info(
+                default_builder_opt_for_test(),
+                object_id as u64,
+                (start_index..end_index)
+                    .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+                sstable_store.clone(),
+            )
+            .await;
+            table_infos.push(table_info);
+        }
+
+        // The upper bound falls strictly inside the third table, well away from any block
+        // boundary, so a naive implementation would keep reading past it.
+        let start_index = 0;
+        let upper_bound_index = 2 * TEST_KEYS_COUNT + TEST_KEYS_COUNT / 2 + 37;
+        let kr = KeyRange::new(
+            test_key_of(start_index).encode().into(),
+            test_key_of(upper_bound_index).encode().into(),
+        );
+        let mut iter =
+            ConcatSstableIterator::for_test(vec![0], table_infos, kr.clone(), sstable_store);
+        iter.seek(FullKey::decode(&kr.left)).await.unwrap();
+
+        let mut idx = start_index;
+        while iter.is_valid() {
+            assert_eq!(iter.key(), test_key_of(idx).to_ref());
+            idx += 1;
+            iter.next().await.unwrap();
+        }
+        // `key_range.right` is inclusive by default (`right_exclusive == false`), so the bound
+        // key itself is the last one yielded.
+        assert_eq!(idx, upper_bound_index + 1);
+    }
+
+    #[tokio::test]
+    async fn test_concat_iterator_new_bounded() {
+        let sstable_store = mock_sstable_store();
+        let mut table_infos = vec![];
+        for object_id in 0..4 {
+            let start_index = object_id * TEST_KEYS_COUNT;
+            let end_index = (object_id + 1) * TEST_KEYS_COUNT;
+            let (_table, mut table_info) = gen_test_sstable_and_info(
+                default_builder_opt_for_test(),
+                object_id as u64,
+                (start_index..end_index)
+                    .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+                sstable_store.clone(),
+            )
+            .await;
+            // Pretend each table is 10 MB so we can exercise the byte budget regardless of the
+            // actual (small) size of the generated test SST.
+            table_info.file_size = 10 * 1024 * 1024;
+            table_infos.push(table_info);
+        }
+
+        let kr = KeyRange::new(Vec::new().into(), Vec::new().into());
+        let (_iter, deferred) = super::ConcatSstableIterator::new_bounded(
+            vec![0],
+            table_infos.clone(),
+            kr,
+            sstable_store.clone(),
+            Arc::new(crate::hummock::compactor::task_progress::TaskProgress::default()),
+            25 * 1024 * 1024,
+        );
+        assert_eq!(deferred.len(), 2);
+        assert_eq!(deferred[0].object_id, table_infos[2].object_id);
+        assert_eq!(deferred[1].object_id, table_infos[3].object_id);
+    }
+
     #[tokio::test]
     async fn test_concat_iterator_seek_idx() {
         let sstable_store = mock_sstable_store();
@@ -685,4 +1128,206 @@ mod tests {
         assert!(iter.is_valid());
         assert_eq!(iter.key(), block_1_second_key.to_ref());
     }
+
+    #[tokio::test]
+    async fn test_concat_iterator_dedup_table_boundary_user_key() {
+        let sstable_store = mock_sstable_store();
+
+        // Table 0 ends with `idx=TEST_KEYS_COUNT - 1` at the newer epoch (300); table 1 starts
+        // with the same user key at an older epoch (100), as happens when a single user key's
+        // version run is split across a compaction boundary.
+        let boundary_idx = TEST_KEYS_COUNT - 1;
+        let (_table0, table_info0) = gen_test_sstable_and_info(
+            default_builder_opt_for_test(),
+            0,
+            (0..boundary_idx)
+                .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i))))
+                .chain([(
+                    FullKey::for_test(TableId::default(), test_user_key_of(boundary_idx).table_key.0, 300),
+                    HummockValue::put(test_value_of(boundary_idx)),
+                )]),
+            sstable_store.clone(),
+        )
+        .await;
+        let (_table1, table_info1) = gen_test_sstable_and_info(
+            default_builder_opt_for_test(),
+            1,
+            [(
+                FullKey::for_test(TableId::default(), test_user_key_of(boundary_idx).table_key.0, 100),
+                HummockValue::put(b"stale".to_vec()),
+            )]
+            .into_iter()
+            .chain(
+                (boundary_idx + 1..boundary_idx + 1 + TEST_KEYS_COUNT)
+                    .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+            ),
+            sstable_store.clone(),
+        )
+        .await;
+
+        let kr = KeyRange::new(Vec::new().into(), Vec::new().into());
+        let mut iter = ConcatSstableIterator::for_test(
+            vec![0],
+            vec![table_info0, table_info1],
+            kr,
+            sstable_store,
+        )
+        .with_dedup_table_boundary_user_key(true);
+        iter.rewind().await.unwrap();
+
+        let mut count = 0;
+        let mut last_value = None;
+        while iter.is_valid() {
+            if count == boundary_idx {
+                // The newer (epoch 300) boundary version survives; the stale (epoch 100)
+                // duplicate from table 1 must have been skipped.
+                assert_eq!(iter.key().epoch, 300);
+                assert_eq!(
+                    iter.value().into_user_value().unwrap(),
+                    test_value_of(boundary_idx).as_slice()
+                );
+            }
+            last_value = Some(iter.value().into_user_value().unwrap().to_vec());
+            count += 1;
+            iter.next().await.unwrap();
+        }
+        assert_eq!(count, boundary_idx + 1 + TEST_KEYS_COUNT);
+        assert_eq!(last_value.unwrap(), test_value_of(boundary_idx + TEST_KEYS_COUNT));
+    }
+
+    #[tokio::test]
+    async fn test_concat_iterator_shared_prefetch_budget() {
+        let sstable_store = mock_sstable_store();
+        let mut table_infos = vec![];
+        for object_id in 0..4 {
+            let start_index = object_id * TEST_KEYS_COUNT;
+            let end_index = (object_id + 1) * TEST_KEYS_COUNT;
+            let (_table, table_info) = gen_test_sstable_and_info(
+                default_builder_opt_for_test(),
+                object_id as u64,
+                (start_index..end_index)
+                    .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+                sstable_store.clone(),
+            )
+            .await;
+            table_infos.push(table_info);
+        }
+        let total_bytes: u64 = table_infos.iter().map(|t| t.file_size).sum();
+
+        // The budget is tight enough that not every table's bytes fit, forcing at least one of
+        // the two sibling iterators below to fall back to on-demand loading.
+        let budget = Arc::new(super::PrefetchBudget::new(total_bytes / 2));
+
+        let kr = KeyRange::new(Vec::new().into(), Vec::new().into());
+        let mut iter0 = ConcatSstableIterator::for_test(
+            vec![0],
+            table_infos[0..2].to_vec(),
+            kr.clone(),
+            sstable_store.clone(),
+        )
+        .with_prefetch_budget(budget.clone());
+        let mut iter1 =
+            ConcatSstableIterator::for_test(vec![0], table_infos[2..4].to_vec(), kr, sstable_store)
+                .with_prefetch_budget(budget.clone());
+
+        iter0.rewind().await.unwrap();
+        let mut count0 = 0;
+        while iter0.is_valid() {
+            assert_eq!(iter0.key(), test_key_of(count0).to_ref());
+            count0 += 1;
+            iter0.next().await.unwrap();
+        }
+        assert_eq!(count0, 2 * TEST_KEYS_COUNT);
+
+        iter1.rewind().await.unwrap();
+        let mut count1 = 0;
+        while iter1.is_valid() {
+            assert_eq!(iter1.key(), test_key_of(2 * TEST_KEYS_COUNT + count1).to_ref());
+            count1 += 1;
+            iter1.next().await.unwrap();
+        }
+        assert_eq!(count1, 2 * TEST_KEYS_COUNT);
+
+        // Both iterators scanned correctly regardless of which path served their tables, and the
+        // shared budget never let total prefetched bytes exceed the configured ceiling.
+        assert!(budget.used() <= total_bytes / 2);
+    }
+
+    #[tokio::test]
+    async fn test_concat_iterator_epoch_range() {
+        let sstable_store = mock_sstable_store();
+        let kvs = (0..10)
+            .map(|i| {
+                let epoch = (i + 1) as u64;
+                (
+                    FullKey {
+                        user_key: test_user_key_of(i),
+                        epoch,
+                    },
+                    HummockValue::put(test_value_of(i)),
+                )
+            })
+            .collect_vec();
+        let (_table, table_info) = gen_test_sstable_and_info(
+            default_builder_opt_for_test(),
+            0,
+            kvs.into_iter(),
+            sstable_store.clone(),
+        )
+        .await;
+
+        let mut iter = ConcatSstableIterator::for_test(
+            vec![0],
+            vec![table_info],
+            KeyRange::inf(),
+            sstable_store,
+        )
+        .with_epoch_range(4, 6);
+        iter.rewind().await.unwrap();
+
+        let mut epochs = vec![];
+        while iter.is_valid() {
+            epochs.push(iter.key().epoch);
+            iter.next().await.unwrap();
+        }
+        assert_eq!(epochs, vec![4, 5, 6]);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_range_rows() {
+        let sstable_store = mock_sstable_store();
+        let mut table_infos = vec![];
+        for object_id in 0..3 {
+            let start_index = object_id * TEST_KEYS_COUNT;
+            let end_index = (object_id + 1) * TEST_KEYS_COUNT;
+            let (_table, table_info) = gen_test_sstable_and_info(
+                default_builder_opt_for_test(),
+                object_id as u64,
+                (start_index..end_index)
+                    .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+                sstable_store.clone(),
+            )
+            .await;
+            table_infos.push(table_info);
+        }
+        let iter =
+            ConcatSstableIterator::for_test(vec![0], table_infos, KeyRange::inf(), sstable_store);
+
+        // A mid-range window straddling the boundary between the first and second table, and
+        // between the second and third table.
+        let lo = test_key_of(9000);
+        let hi = test_key_of(21000);
+        let true_count = 21000 - 9000 + 1;
+
+        let estimate = iter.estimate_range_rows(lo.to_ref(), hi.to_ref());
+        // The estimate is coarse (it assumes keys are evenly spread within a straddled table), so
+        // only check it lands within the same order of magnitude as the true count.
+        let diff = (estimate as i64 - true_count as i64).unsigned_abs();
+        assert!(
+            diff <= true_count as u64,
+            "estimate {} too far from true count {}",
+            estimate,
+            true_count
+        );
+    }
 }
@@ -32,12 +32,12 @@ use await_tree::InstrumentAwait;
 pub use compaction_executor::CompactionExecutor;
 pub use compaction_filter::{
     CompactionFilter, DummyCompactionFilter, MultiCompactionFilter, StateCleanUpCompactionFilter,
-    TtlCompactionFilter,
+    TtlCompactionFilter, WatermarkCompactionFilter,
 };
 pub use context::CompactorContext;
 use futures::future::try_join_all;
 use futures::{pin_mut, stream, FutureExt, StreamExt};
-pub use iterator::ConcatSstableIterator;
+pub use iterator::{ConcatSstableIterator, PrefetchBudget};
 use itertools::Itertools;
 use more_asserts::assert_ge;
 use risingwave_hummock_sdk::compact::{compact_task_to_string, estimate_state_for_compaction};
@@ -1064,7 +1064,10 @@ impl Compactor {
             self.task_config.is_target_l0_or_lbase,
             self.task_config.split_by_table,
             self.task_config.split_weight_by_vnode,
-        );
+        )
+        // Overlap a sealed table's block/bloom-filter encoding and upload with the next
+        // table's ingestion, instead of blocking `add_full_key` until it's done.
+        .with_concurrent_finish(2);
         let compaction_statistics = Compactor::compact_and_build_sst(
             &mut sst_builder,
             &self.task_config,
@@ -41,6 +41,10 @@ enum HummockErrorInner {
     MetaError(String),
     #[error("Invalid WriteBatch.")]
     InvalidWriteBatch,
+    #[error("Invalid SST key range: {0}.")]
+    InvalidSstKeyRange(String),
+    #[error("Unsorted SST key: {0}.")]
+    UnsortedSstKey(String),
     #[error("SharedBuffer error {0}.")]
     SharedBufferError(String),
     #[error("Wait epoch error {0}.")]
@@ -61,6 +65,8 @@ enum HummockErrorInner {
     SstableUploadError(String),
     #[error("Read backup error {0}.")]
     ReadBackupError(String),
+    #[error("Iterator is invalid, cannot advance further.")]
+    IteratorInvalid,
     #[error("Other error {0}.")]
     Other(String),
 }
@@ -110,6 +116,14 @@ impl HummockError {
         HummockErrorInner::InvalidWriteBatch.into()
     }
 
+    pub fn invalid_sst_key_range(error: impl ToString) -> HummockError {
+        HummockErrorInner::InvalidSstKeyRange(error.to_string()).into()
+    }
+
+    pub fn unsorted_sst_key(error: impl ToString) -> HummockError {
+        HummockErrorInner::UnsortedSstKey(error.to_string()).into()
+    }
+
     pub fn shared_buffer_error(error: impl ToString) -> HummockError {
         HummockErrorInner::SharedBufferError(error.to_string()).into()
     }
@@ -134,6 +148,15 @@ impl HummockError {
         matches!(self.inner, HummockErrorInner::MetaError(..))
     }
 
+    /// Whether this error came from the object store layer rather than from decoding or
+    /// validating an SST that was already fetched. Object-store failures are often transient
+    /// (e.g. a network blip), so callers loading an SST may choose to retry on this but not on
+    /// e.g. [`HummockErrorInner::DecodeError`] or [`HummockErrorInner::InvalidSstKeyRange`],
+    /// which indicate genuine corruption that retrying cannot fix.
+    pub fn is_object_io_error(&self) -> bool {
+        matches!(self.inner, HummockErrorInner::ObjectIoError(..))
+    }
+
     pub fn compaction_executor(error: impl ToString) -> HummockError {
         HummockErrorInner::CompactionExecutor(error.to_string()).into()
     }
@@ -161,6 +184,14 @@ impl HummockError {
     pub fn other(error: impl ToString) -> HummockError {
         HummockErrorInner::Other(error.to_string()).into()
     }
+
+    pub fn iterator_invalid() -> HummockError {
+        HummockErrorInner::IteratorInvalid.into()
+    }
+
+    pub fn is_iterator_invalid(&self) -> bool {
+        matches!(self.inner, HummockErrorInner::IteratorInvalid)
+    }
 }
 
 impl From<prost::DecodeError> for HummockError {
@@ -538,6 +538,44 @@ mod tests {
         assert!(!bui.is_valid());
     }
 
+    #[tokio::test]
+    async fn test_backward_user_delete_at_top_epoch() {
+        let sstable_store = mock_sstable_store();
+        // key 1 has three versions, stored newest-epoch-first within the SST; the delete at the
+        // top epoch (300) must mask the older puts (200, 100) of the same key.
+        let kv_pairs = vec![
+            (1, 300, HummockValue::delete()),
+            (1, 200, HummockValue::put(iterator_test_value_of(1))),
+            (1, 100, HummockValue::put(iterator_test_value_of(1))),
+            (2, 100, HummockValue::put(iterator_test_value_of(2))),
+        ];
+        let table0 =
+            gen_iterator_test_sstable_from_kv_pair(0, kv_pairs, sstable_store.clone()).await;
+        let cache = create_small_table_cache();
+        let backward_iters = vec![BackwardSstableIterator::new(
+            cache.insert(
+                table0.id,
+                table0.id,
+                1,
+                Box::new(table0),
+                CachePriority::High,
+            ),
+            sstable_store,
+        )];
+        let bmi = UnorderedMergeIteratorInner::new(backward_iters);
+        let mut bui = BackwardUserIterator::for_test(bmi, (Unbounded, Unbounded));
+
+        bui.rewind().await.unwrap();
+
+        // key 1 is entirely masked by its own delete, so the only surviving key is key 2.
+        assert!(bui.is_valid());
+        assert_eq!(bui.key(), &iterator_test_bytes_key_of_epoch(2, 100));
+        assert_eq!(bui.value(), &Bytes::from(iterator_test_value_of(2)));
+
+        bui.next().await.unwrap();
+        assert!(!bui.is_valid());
+    }
+
     // left..=end
     #[tokio::test]
     async fn test_backward_user_range_inclusive() {
@@ -0,0 +1,212 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+
+use risingwave_hummock_sdk::key::FullKey;
+
+use super::{DirectionEnum, HummockIterator, HummockIteratorDirection};
+use crate::hummock::value::HummockValue;
+use crate::hummock::HummockResult;
+use crate::monitor::StoreLocalStatistic;
+
+/// Exhausts `first`, then transparently continues with `second`, exposing the combined scan as
+/// a single [`HummockIterator`]. Meant for chaining two known-non-overlapping runs (e.g. an L0
+/// overlapping run followed by a concat of deeper, non-overlapping levels) into one logical
+/// stream without going through a merge iterator.
+///
+/// `first` and `second` must together cover disjoint, already correctly-ordered key ranges:
+/// `second_start` is the boundary key at which `second`'s range begins (in iteration order), and
+/// is used by [`Self::seek`] to decide which of the two iterators a seek key should route to.
+pub struct Chain<A: HummockIterator, B: HummockIterator<Direction = A::Direction>> {
+    first: A,
+    second: B,
+    /// The smallest key (largest, for a backward iterator) that belongs to `second`'s range.
+    second_start: Vec<u8>,
+    /// Whether `key()`/`value()` should currently be read off `first` rather than `second`.
+    on_first: bool,
+}
+
+impl<A: HummockIterator, B: HummockIterator<Direction = A::Direction>> Chain<A, B> {
+    pub fn new(first: A, second: B, second_start: FullKey<Vec<u8>>) -> Self {
+        Self {
+            first,
+            second,
+            second_start: second_start.encode(),
+            on_first: true,
+        }
+    }
+
+    fn covered_by_second(&self, key: FullKey<&[u8]>) -> bool {
+        let second_start = FullKey::decode(&self.second_start);
+        match A::Direction::direction() {
+            DirectionEnum::Forward => key >= second_start,
+            DirectionEnum::Backward => key <= second_start,
+        }
+    }
+}
+
+impl<A: HummockIterator, B: HummockIterator<Direction = A::Direction>> HummockIterator
+    for Chain<A, B>
+{
+    type Direction = A::Direction;
+
+    type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+    fn next(&mut self) -> Self::NextFuture<'_> {
+        async move {
+            if self.on_first {
+                self.first.next().await?;
+                if !self.first.is_valid() {
+                    self.on_first = false;
+                    self.second.rewind().await?;
+                }
+            } else {
+                self.second.next().await?;
+            }
+            Ok(())
+        }
+    }
+
+    fn key(&self) -> FullKey<&[u8]> {
+        if self.on_first {
+            self.first.key()
+        } else {
+            self.second.key()
+        }
+    }
+
+    fn value(&self) -> HummockValue<&[u8]> {
+        if self.on_first {
+            self.first.value()
+        } else {
+            self.second.value()
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        if self.on_first {
+            self.first.is_valid()
+        } else {
+            self.second.is_valid()
+        }
+    }
+
+    fn rewind(&mut self) -> Self::RewindFuture<'_> {
+        async move {
+            self.on_first = true;
+            self.first.rewind().await?;
+            if !self.first.is_valid() {
+                self.on_first = false;
+                self.second.rewind().await?;
+            }
+            Ok(())
+        }
+    }
+
+    fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+        async move {
+            if self.covered_by_second(key) {
+                self.on_first = false;
+                self.second.seek(key).await?;
+            } else {
+                self.on_first = true;
+                self.first.seek(key).await?;
+                if !self.first.is_valid() {
+                    self.on_first = false;
+                    self.second.rewind().await?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn collect_local_statistic(&self, stats: &mut StoreLocalStatistic) {
+        self.first.collect_local_statistic(stats);
+        self.second.collect_local_statistic(stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::{
+        default_builder_opt_for_test, gen_iterator_test_sstable_base, iterator_test_key_of,
+        mock_sstable_store, TEST_KEYS_COUNT,
+    };
+    use crate::hummock::iterator::ConcatIterator;
+    use crate::hummock::sstable::SstableIteratorReadOptions;
+
+    #[tokio::test]
+    async fn test_chain_two_concat_iterators() {
+        let sstable_store = mock_sstable_store();
+        let table0 = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let table1 = gen_iterator_test_sstable_base(
+            1,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+
+        let first = ConcatIterator::new(
+            vec![table0.get_sstable_info()],
+            sstable_store.clone(),
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        let second = ConcatIterator::new(
+            vec![table1.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        let second_start = iterator_test_key_of(TEST_KEYS_COUNT);
+        let mut chain = Chain::new(first, second, second_start);
+
+        chain.rewind().await.unwrap();
+        for i in 0..TEST_KEYS_COUNT * 2 {
+            assert!(chain.is_valid());
+            assert_eq!(chain.key(), iterator_test_key_of(i).to_ref());
+            chain.next().await.unwrap();
+        }
+        assert!(!chain.is_valid());
+
+        // Seeking into the boundary of `second` should route there directly.
+        chain
+            .seek(iterator_test_key_of(TEST_KEYS_COUNT + 2).to_ref())
+            .await
+            .unwrap();
+        assert!(chain.is_valid());
+        assert_eq!(
+            chain.key(),
+            iterator_test_key_of(TEST_KEYS_COUNT + 2).to_ref()
+        );
+
+        // Seeking into `first`'s range should route there.
+        chain.seek(iterator_test_key_of(3).to_ref()).await.unwrap();
+        assert!(chain.is_valid());
+        assert_eq!(chain.key(), iterator_test_key_of(3).to_ref());
+    }
+}
@@ -14,15 +14,17 @@
 
 use std::cmp::Ordering::{Equal, Greater, Less};
 use std::future::Future;
+use std::ops::Bound;
 use std::sync::Arc;
 
-use risingwave_hummock_sdk::key::FullKey;
+use risingwave_hummock_sdk::key::{FullKey, UserKey};
 use risingwave_pb::hummock::SstableInfo;
 
-use crate::hummock::iterator::{DirectionEnum, HummockIterator, HummockIteratorDirection};
+use crate::hummock::iterator::{debug_key, DirectionEnum, HummockIterator, HummockIteratorDirection};
 use crate::hummock::sstable::SstableIteratorReadOptions;
+use crate::hummock::utils::{range_overlap, retry_sstable_load};
 use crate::hummock::value::HummockValue;
-use crate::hummock::{HummockResult, SstableIteratorType, SstableStoreRef};
+use crate::hummock::{HummockError, HummockResult, Sstable, SstableIteratorType, SstableStoreRef};
 use crate::monitor::StoreLocalStatistic;
 
 fn smallest_key(sstable_info: &SstableInfo) -> &[u8] {
@@ -48,6 +50,35 @@ pub struct ConcatIteratorInner<TI: SstableIteratorType> {
 
     stats: StoreLocalStatistic,
     read_options: Arc<SstableIteratorReadOptions>,
+
+    /// Set by a [`HummockIterator::rewind`] under `read_options.lazy_rewind`, meaning table 0
+    /// has not actually been loaded yet. Cleared by the first `next`/`seek` afterwards, which
+    /// performs the deferred load. See the doc comment on
+    /// [`SstableIteratorReadOptions::lazy_rewind`] for the accompanying caveat on `is_valid`/
+    /// `key`/`value`.
+    pending_rewind: bool,
+}
+
+/// A cheaply-clonable snapshot of a [`ConcatIteratorInner`]'s configuration, obtained via
+/// [`ConcatIteratorInner::fork_config`]. Carries everything needed to scan the same tables
+/// again but holds none of the mutable cursor state, so it can be handed to several worker
+/// tasks that each build their own independent iterator via [`ConcatIteratorInner::from_config`].
+pub struct ConcatIteratorConfig<TI: SstableIteratorType> {
+    tables: Vec<SstableInfo>,
+    sstable_store: SstableStoreRef,
+    read_options: Arc<SstableIteratorReadOptions>,
+    _phantom: std::marker::PhantomData<TI>,
+}
+
+impl<TI: SstableIteratorType> Clone for ConcatIteratorConfig<TI> {
+    fn clone(&self) -> Self {
+        Self {
+            tables: self.tables.clone(),
+            sstable_store: self.sstable_store.clone(),
+            read_options: self.read_options.clone(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
 }
 
 impl<TI: SstableIteratorType> ConcatIteratorInner<TI> {
@@ -59,6 +90,9 @@ impl<TI: SstableIteratorType> ConcatIteratorInner<TI> {
         sstable_store: SstableStoreRef,
         read_options: Arc<SstableIteratorReadOptions>,
     ) -> Self {
+        if cfg!(debug_assertions) {
+            Self::debug_assert_non_overlapping_and_ordered(&tables);
+        }
         Self {
             sstable_iter: None,
             cur_idx: 0,
@@ -66,9 +100,126 @@ impl<TI: SstableIteratorType> ConcatIteratorInner<TI> {
             sstable_store,
             stats: StoreLocalStatistic::default(),
             read_options,
+            pending_rewind: false,
+        }
+    }
+
+    /// Cheaply (no async, no block or even SST meta loads) checks whether this iterator is
+    /// guaranteed to yield nothing. Returns `true` only when there are no tables to scan at all;
+    /// a `false` does not mean the iterator is non-empty, since that can't be proven without
+    /// loading the first table. Meant for callers (e.g. a query planner) that want to skip
+    /// scheduling work for provably empty ranges before committing to a scan.
+    pub fn is_definitely_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Clears the [`StoreLocalStatistic`] accumulated so far, so a reused iterator's next
+    /// [`HummockIterator::collect_local_statistic`] call only reports reads from its next logical
+    /// scan. Note that [`HummockIterator::rewind`] does **not** call this implicitly, to preserve
+    /// the existing behaviour of accumulating stats across rewinds of the same scan; callers that
+    /// reuse an iterator for an unrelated scan must call this themselves first.
+    pub fn reset_local_statistic(&mut self) {
+        self.stats = StoreLocalStatistic::default();
+    }
+
+    /// Clones this iterator's read-only configuration (tables, sstable store, read options),
+    /// leaving behind the mutable cursor state (`sstable_iter`, `cur_idx`, `stats`). Meant to be
+    /// consumed by [`Self::from_config`] to build several independent iterators, e.g. one per
+    /// worker task scanning a disjoint key sub-range in parallel.
+    pub fn fork_config(&self) -> ConcatIteratorConfig<TI> {
+        ConcatIteratorConfig {
+            tables: self.tables.clone(),
+            sstable_store: self.sstable_store.clone(),
+            read_options: self.read_options.clone(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Builds a new iterator from a forked config, restricted to the tables overlapping
+    /// `user_key_range`. `config.tables` must still be non-overlapping and ordered consistent
+    /// with `TI::Direction`, same as required by [`Self::new`].
+    pub fn from_config(
+        config: ConcatIteratorConfig<TI>,
+        user_key_range: (Bound<UserKey<&[u8]>>, Bound<UserKey<&[u8]>>),
+    ) -> Self {
+        let tables = config
+            .tables
+            .into_iter()
+            .filter(|table| Self::table_overlaps_range(table, &user_key_range))
+            .collect();
+        Self::new(tables, config.sstable_store, config.read_options)
+    }
+
+    /// Whether `table`'s key range overlaps `user_key_range`, used by [`Self::from_config`] to
+    /// select the subset of a forked config's tables relevant to one sub-range scan.
+    fn table_overlaps_range(
+        table: &SstableInfo,
+        user_key_range: &(Bound<UserKey<&[u8]>>, Bound<UserKey<&[u8]>>),
+    ) -> bool {
+        let key_range = table.key_range.as_ref().unwrap();
+        let table_start = FullKey::decode(&key_range.left).user_key;
+        let table_end = FullKey::decode(&key_range.right).user_key;
+        range_overlap(
+            user_key_range,
+            &table_start,
+            if key_range.right_exclusive {
+                Bound::Excluded(&table_end)
+            } else {
+                Bound::Included(&table_end)
+            },
+        )
+    }
+
+    /// Verifies that `tables` are non-overlapping and arranged in the correct order for
+    /// [`TI::Direction`]. This is a no-op in release builds: the check only exists to turn a
+    /// violated invariant into a clear panic instead of silently wrong iteration results.
+    fn debug_assert_non_overlapping_and_ordered(tables: &[SstableInfo]) {
+        for i in 1..tables.len() {
+            let (earlier, later) = match TI::Direction::direction() {
+                DirectionEnum::Forward => (&tables[i - 1], &tables[i]),
+                DirectionEnum::Backward => (&tables[i], &tables[i - 1]),
+            };
+            let earlier_largest = FullKey::decode(largest_key(earlier));
+            let later_smallest = FullKey::decode(smallest_key(later));
+            let ord = earlier_largest.cmp(&later_smallest);
+            let ok = ord == Less
+                || (ord == Equal && earlier.key_range.as_ref().unwrap().right_exclusive);
+            assert!(
+                ok,
+                "ConcatIteratorInner received overlapping or out-of-order tables at indices \
+                 {} and {}: table {} (largest key {}) does not come strictly before table {} \
+                 (smallest key {})",
+                i - 1,
+                i,
+                earlier.sst_id,
+                debug_key(largest_key(earlier)),
+                later.sst_id,
+                debug_key(smallest_key(later)),
+            );
         }
     }
 
+    /// Asserts that `sstable`'s actual smallest/largest key (as recorded in its own meta) is
+    /// consistent with `table_info.key_range`, the metadata used to binary-search it. A mismatch
+    /// means the meta store's `key_range` is stale relative to the SST it points at (e.g. after a
+    /// crash mid-compaction), which would otherwise make `seek` silently land on the wrong table.
+    fn verify_key_range(table_info: &SstableInfo, sstable: &Sstable) -> HummockResult<()> {
+        let key_range = table_info.key_range.as_ref().unwrap();
+        if sstable.meta.smallest_key != key_range.left || sstable.meta.largest_key != key_range.right
+        {
+            return Err(HummockError::invalid_sst_key_range(format!(
+                "sst {} key_range mismatch: meta store key_range is [{:?}, {:?}], but loaded \
+                 sstable's actual key range is [{:?}, {:?}]",
+                table_info.sst_id,
+                key_range.left,
+                key_range.right,
+                sstable.meta.smallest_key,
+                sstable.meta.largest_key,
+            )));
+        }
+        Ok(())
+    }
+
     /// Seeks to a table, and then seeks to the key if `seek_key` is given.
     async fn seek_idx(
         &mut self,
@@ -80,10 +231,15 @@ impl<TI: SstableIteratorType> ConcatIteratorInner<TI> {
                 old_iter.collect_local_statistic(&mut self.stats);
             }
         } else {
-            let table = self
-                .sstable_store
-                .sstable(&self.tables[idx], &mut self.stats)
-                .await?;
+            let table = retry_sstable_load(&self.read_options.load_retry_options, || {
+                self.sstable_store.sstable(&self.tables[idx], &mut self.stats)
+            })
+            .await?;
+
+            if self.read_options.verify_key_range {
+                Self::verify_key_range(&self.tables[idx], table.value())?;
+            }
+
             let mut sstable_iter =
                 TI::create(table, self.sstable_store.clone(), self.read_options.clone());
 
@@ -113,6 +269,11 @@ impl<TI: SstableIteratorType> HummockIterator for ConcatIteratorInner<TI> {
 
     fn next(&mut self) -> Self::NextFuture<'_> {
         async move {
+            if self.pending_rewind {
+                self.pending_rewind = false;
+                return self.seek_idx(0, None).await;
+            }
+
             let sstable_iter = self.sstable_iter.as_mut().expect("no table iter");
             sstable_iter.next().await?;
 
@@ -138,11 +299,23 @@ impl<TI: SstableIteratorType> HummockIterator for ConcatIteratorInner<TI> {
     }
 
     fn rewind(&mut self) -> Self::RewindFuture<'_> {
-        async move { self.seek_idx(0, None).await }
+        async move {
+            if self.read_options.lazy_rewind {
+                if let Some(old_iter) = self.sstable_iter.take() {
+                    old_iter.collect_local_statistic(&mut self.stats);
+                }
+                self.cur_idx = 0;
+                self.pending_rewind = true;
+                Ok(())
+            } else {
+                self.seek_idx(0, None).await
+            }
+        }
     }
 
     fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
         async move {
+            self.pending_rewind = false;
             let table_idx = self
                 .tables
                 .partition_point(|table| match Self::Direction::direction() {
@@ -0,0 +1,191 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_hummock_sdk::key::FullKey;
+use risingwave_pb::hummock::SstableInfo;
+
+use super::{BackwardConcatIterator, ConcatIterator, DirectionEnum, HummockIterator};
+use crate::hummock::sstable::SstableIteratorReadOptions;
+use crate::hummock::value::HummockValue;
+use crate::hummock::{HummockResult, SstableStoreRef};
+use crate::monitor::StoreLocalStatistic;
+
+/// A [`ConcatIterator`]/[`BackwardConcatIterator`] that picks its scan direction at runtime
+/// instead of at the type level, for operators (e.g. a scan whose order comes from the query
+/// plan's `ORDER BY`) that don't know ascending vs. descending until the plan is built and would
+/// otherwise have to duplicate their call site per direction.
+///
+/// This intentionally does NOT implement [`HummockIterator`]: that trait's `Direction` associated
+/// type is relied upon by generic code (e.g. `UserIterator<I: HummockIterator<Direction =
+/// Forward>>`) to reason about key order at compile time, and a value whose actual iteration
+/// order can flip at runtime cannot honestly claim a single, fixed `Direction`. Callers that pick
+/// direction at runtime are expected to consume this type directly, the same way they already
+/// branch on direction to decide which one to construct.
+pub enum DynDirectionConcatIterator {
+    Forward(ConcatIterator),
+    Backward(BackwardConcatIterator),
+}
+
+impl DynDirectionConcatIterator {
+    /// `tables` must be provided in ascending key order, regardless of `direction`; this mirrors
+    /// how tables are naturally stored and ordered in a `SstableInfo` list. When `direction` is
+    /// [`DirectionEnum::Backward`], the table order is reversed internally to satisfy
+    /// `ConcatIteratorInner`'s requirement that tables be descending for backward iteration.
+    pub fn new(
+        direction: DirectionEnum,
+        mut tables: Vec<SstableInfo>,
+        sstable_store: SstableStoreRef,
+        read_options: Arc<SstableIteratorReadOptions>,
+    ) -> Self {
+        match direction {
+            DirectionEnum::Forward => {
+                Self::Forward(ConcatIterator::new(tables, sstable_store, read_options))
+            }
+            DirectionEnum::Backward => {
+                tables.reverse();
+                Self::Backward(BackwardConcatIterator::new(
+                    tables,
+                    sstable_store,
+                    read_options,
+                ))
+            }
+        }
+    }
+
+    pub fn direction(&self) -> DirectionEnum {
+        match self {
+            Self::Forward(_) => DirectionEnum::Forward,
+            Self::Backward(_) => DirectionEnum::Backward,
+        }
+    }
+
+    pub async fn rewind(&mut self) -> HummockResult<()> {
+        match self {
+            Self::Forward(iter) => iter.rewind().await,
+            Self::Backward(iter) => iter.rewind().await,
+        }
+    }
+
+    pub async fn next(&mut self) -> HummockResult<()> {
+        match self {
+            Self::Forward(iter) => iter.next().await,
+            Self::Backward(iter) => iter.next().await,
+        }
+    }
+
+    pub fn key(&self) -> FullKey<&[u8]> {
+        match self {
+            Self::Forward(iter) => iter.key(),
+            Self::Backward(iter) => iter.key(),
+        }
+    }
+
+    pub fn value(&self) -> HummockValue<&[u8]> {
+        match self {
+            Self::Forward(iter) => iter.value(),
+            Self::Backward(iter) => iter.value(),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Forward(iter) => iter.is_valid(),
+            Self::Backward(iter) => iter.is_valid(),
+        }
+    }
+
+    pub fn collect_local_statistic(&self, stats: &mut StoreLocalStatistic) {
+        match self {
+            Self::Forward(iter) => iter.collect_local_statistic(stats),
+            Self::Backward(iter) => iter.collect_local_statistic(stats),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::{
+        default_builder_opt_for_test, gen_iterator_test_sstable_base, iterator_test_key_of,
+        mock_sstable_store, TEST_KEYS_COUNT,
+    };
+
+    #[tokio::test]
+    async fn test_dyn_direction_concat_iterator() {
+        let sstable_store = mock_sstable_store();
+        let table0 = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let table1 = gen_iterator_test_sstable_base(
+            1,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let table2 = gen_iterator_test_sstable_base(
+            2,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT * 2 + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let tables = vec![
+            table0.get_sstable_info(),
+            table1.get_sstable_info(),
+            table2.get_sstable_info(),
+        ];
+
+        // Ascending order.
+        let mut iter = DynDirectionConcatIterator::new(
+            DirectionEnum::Forward,
+            tables.clone(),
+            sstable_store.clone(),
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        iter.rewind().await.unwrap();
+        for i in 0..TEST_KEYS_COUNT * 3 {
+            assert!(iter.is_valid());
+            assert_eq!(iter.key(), iterator_test_key_of(i).to_ref());
+            iter.next().await.unwrap();
+        }
+        assert!(!iter.is_valid());
+
+        // Descending order, same tables.
+        let mut iter = DynDirectionConcatIterator::new(
+            DirectionEnum::Backward,
+            tables,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        iter.rewind().await.unwrap();
+        for i in (0..TEST_KEYS_COUNT * 3).rev() {
+            assert!(iter.is_valid());
+            assert_eq!(iter.key(), iterator_test_key_of(i).to_ref());
+            iter.next().await.unwrap();
+        }
+        assert!(!iter.is_valid());
+    }
+}
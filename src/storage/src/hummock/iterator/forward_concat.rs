@@ -24,11 +24,13 @@ mod tests {
 
     use super::*;
     use crate::hummock::iterator::test_utils::{
-        default_builder_opt_for_test, gen_iterator_test_sstable_base, iterator_test_key_of,
+        default_builder_opt_for_test, gen_iterator_test_sstable_base,
+        gen_iterator_test_sstable_from_kv_pair, iterator_test_key_of, iterator_test_key_of_epoch,
         iterator_test_value_of, mock_sstable_store, TEST_KEYS_COUNT,
     };
     use crate::hummock::iterator::HummockIterator;
     use crate::hummock::sstable::SstableIteratorReadOptions;
+    use crate::hummock::value::HummockValue;
 
     #[tokio::test]
     async fn test_concat_iterator() {
@@ -95,6 +97,81 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_concat_iterator_fork_config() {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+
+        let sstable_store = mock_sstable_store();
+        let table0 = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let table1 = gen_iterator_test_sstable_base(
+            1,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let table2 = gen_iterator_test_sstable_base(
+            2,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT * 2 + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let tables = vec![
+            table0.get_sstable_info(),
+            table1.get_sstable_info(),
+            table2.get_sstable_info(),
+        ];
+
+        let full_scan_iter = ConcatIterator::new(
+            tables.clone(),
+            sstable_store.clone(),
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        let config = full_scan_iter.fork_config();
+
+        let boundary1 = iterator_test_key_of(TEST_KEYS_COUNT).user_key;
+        let boundary2 = iterator_test_key_of(TEST_KEYS_COUNT * 2).user_key;
+        let sub_ranges = [
+            (Unbounded, Excluded(boundary1.as_ref())),
+            (Included(boundary1.as_ref()), Excluded(boundary2.as_ref())),
+            (Included(boundary2.as_ref()), Unbounded),
+        ];
+
+        let mut keys = Vec::new();
+        for user_key_range in sub_ranges {
+            let mut iter = ConcatIterator::from_config(config.clone(), user_key_range);
+            iter.rewind().await.unwrap();
+            while iter.is_valid() {
+                keys.push(iter.key().to_vec());
+                iter.next().await.unwrap();
+            }
+        }
+
+        let mut full_scan_iter = ConcatIterator::new(
+            tables,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        let mut expected_keys = Vec::new();
+        full_scan_iter.rewind().await.unwrap();
+        while full_scan_iter.is_valid() {
+            expected_keys.push(full_scan_iter.key().to_vec());
+            full_scan_iter.next().await.unwrap();
+        }
+
+        assert_eq!(keys, expected_keys);
+    }
+
     #[tokio::test]
     async fn test_concat_seek() {
         let sstable_store = mock_sstable_store();
@@ -236,4 +313,221 @@ mod tests {
             iterator_test_value_of(TEST_KEYS_COUNT * 4).as_slice()
         );
     }
+
+    #[tokio::test]
+    #[should_panic(expected = "overlapping or out-of-order")]
+    async fn test_concat_debug_assert_rejects_out_of_order_tables() {
+        let sstable_store = mock_sstable_store();
+        let table0 = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let table1 = gen_iterator_test_sstable_base(
+            1,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+
+        // Passed in descending order while building a forward iterator, violating the
+        // documented ordering invariant; should panic with a message naming the indices.
+        let _iter = ConcatIterator::new(
+            vec![table1.get_sstable_info(), table0.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concat_seek_next_user_key() {
+        let sstable_store = mock_sstable_store();
+        let table = gen_iterator_test_sstable_from_kv_pair(
+            0,
+            vec![
+                (0, 5, HummockValue::put(iterator_test_value_of(0))),
+                (0, 4, HummockValue::put(iterator_test_value_of(0))),
+                (0, 3, HummockValue::put(iterator_test_value_of(0))),
+                (0, 2, HummockValue::put(iterator_test_value_of(0))),
+                (0, 1, HummockValue::put(iterator_test_value_of(0))),
+                (1, 233, HummockValue::put(iterator_test_value_of(1))),
+            ],
+            sstable_store.clone(),
+        )
+        .await;
+        let mut iter = ConcatIterator::new(
+            vec![table.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+
+        iter.rewind().await.unwrap();
+        assert_eq!(iter.key(), iterator_test_key_of_epoch(0, 5).to_ref());
+
+        // A single `seek_next_user_key` call should skip all 5 epochs of key 0 and land
+        // directly on the next distinct user key.
+        iter.seek_next_user_key().await.unwrap();
+        assert!(iter.is_valid());
+        assert_eq!(iter.key(), iterator_test_key_of_epoch(1, 233).to_ref());
+        assert_eq!(
+            iter.value().into_user_value().unwrap(),
+            iterator_test_value_of(1).as_slice()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concat_is_definitely_empty() {
+        let iter = ConcatIterator::new(
+            vec![],
+            mock_sstable_store(),
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        assert!(iter.is_definitely_empty());
+
+        let sstable_store = mock_sstable_store();
+        let table0 = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let iter = ConcatIterator::new(
+            vec![table0.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        // Non-empty tables can't be cheaply proven non-empty, so this just returns `false`
+        // without loading anything.
+        assert!(!iter.is_definitely_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concat_verify_key_range_rejects_stale_meta() {
+        let sstable_store = mock_sstable_store();
+        let table = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+
+        // Simulate stale meta store metadata: the `key_range` no longer agrees with the SST's
+        // actual smallest key.
+        let mut table_info = table.get_sstable_info();
+        table_info.key_range.as_mut().unwrap().left = iterator_test_key_of(1).encode();
+
+        let mut iter = ConcatIterator::new(
+            vec![table_info],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions {
+                verify_key_range: true,
+                ..Default::default()
+            }),
+        );
+
+        let err = iter.rewind().await.unwrap_err();
+        assert!(err.to_string().contains("key_range mismatch"));
+    }
+
+    #[tokio::test]
+    async fn test_concat_iterator_reset_local_statistic() {
+        use crate::monitor::StoreLocalStatistic;
+
+        let sstable_store = mock_sstable_store();
+        let table = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let mut iter = ConcatIterator::new(
+            vec![table.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+
+        async fn scan_to_end(iter: &mut ConcatIterator) {
+            iter.rewind().await.unwrap();
+            while iter.is_valid() {
+                iter.next().await.unwrap();
+            }
+        }
+
+        scan_to_end(&mut iter).await;
+        let mut stats_one_scan = StoreLocalStatistic::default();
+        iter.collect_local_statistic(&mut stats_one_scan);
+        assert!(stats_one_scan.cache_data_block_total > 0);
+
+        // Without a reset, rewinding and scanning again accumulates on top of the first scan.
+        scan_to_end(&mut iter).await;
+        let mut stats_two_scans = StoreLocalStatistic::default();
+        iter.collect_local_statistic(&mut stats_two_scans);
+        assert_eq!(
+            stats_two_scans.cache_data_block_total,
+            stats_one_scan.cache_data_block_total * 2
+        );
+
+        // After an explicit reset, a subsequent scan's reported stats reflect only that scan.
+        iter.reset_local_statistic();
+        scan_to_end(&mut iter).await;
+        let mut stats_after_reset = StoreLocalStatistic::default();
+        iter.collect_local_statistic(&mut stats_after_reset);
+        assert_eq!(
+            stats_after_reset.cache_data_block_total,
+            stats_one_scan.cache_data_block_total
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concat_lazy_rewind_defers_load() {
+        use crate::monitor::StoreLocalStatistic;
+
+        let sstable_store = mock_sstable_store();
+        let table = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let mut iter = ConcatIterator::new(
+            vec![table.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions {
+                lazy_rewind: true,
+                ..Default::default()
+            }),
+        );
+
+        iter.rewind().await.unwrap();
+
+        // Nothing has actually been loaded yet: the deferred load only happens on the first
+        // subsequent `next`.
+        let mut stats = StoreLocalStatistic::default();
+        iter.collect_local_statistic(&mut stats);
+        assert_eq!(stats.cache_meta_block_total, 0);
+        assert_eq!(stats.cache_data_block_total, 0);
+
+        iter.next().await.unwrap();
+
+        let mut stats = StoreLocalStatistic::default();
+        iter.collect_local_statistic(&mut stats);
+        assert!(stats.cache_meta_block_total > 0);
+        assert!(stats.cache_data_block_total > 0);
+
+        assert!(iter.is_valid());
+        assert_eq!(iter.key(), iterator_test_key_of(0).to_ref());
+    }
 }
@@ -16,20 +16,23 @@
 mod test {
     use std::future::{pending, poll_fn, Future};
     use std::iter::once;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
     use std::task::Poll;
+    use std::time::Duration;
 
     use futures::{pin_mut, FutureExt};
+    use itertools::Itertools;
     use risingwave_common::cache::CachePriority;
     use risingwave_hummock_sdk::key::{FullKey, TableKey, UserKey};
 
     use crate::hummock::iterator::test_utils::{
         default_builder_opt_for_test, gen_iterator_test_sstable_base,
-        gen_merge_iterator_interleave_test_sstable_iters, iterator_test_key_of,
-        iterator_test_value_of, mock_sstable_store, TEST_KEYS_COUNT,
+        gen_iterator_test_sstable_from_kv_pair, gen_merge_iterator_interleave_test_sstable_iters,
+        iterator_test_key_of, iterator_test_value_of, mock_sstable_store, TEST_KEYS_COUNT,
     };
     use crate::hummock::iterator::{
-        Forward, HummockIterator, HummockIteratorUnion, OrderedMergeIteratorInner,
+        Forward, HummockIterator, HummockIteratorUnion, MergeIterator, OrderedMergeIteratorInner,
         UnorderedMergeIteratorInner,
     };
     use crate::hummock::sstable::{
@@ -37,7 +40,8 @@ mod test {
     };
     use crate::hummock::test_utils::{create_small_table_cache, gen_test_sstable};
     use crate::hummock::value::HummockValue;
-    use crate::hummock::HummockResult;
+    use crate::hummock::TableHolder;
+    use crate::hummock::{HummockResult, MemoryLimiter};
     use crate::monitor::StoreLocalStatistic;
 
     #[tokio::test]
@@ -330,6 +334,50 @@ mod test {
         assert_eq!(count, TEST_KEYS_COUNT);
     }
 
+    #[tokio::test]
+    async fn test_merge_iter_current_source_index() {
+        let sstable_store = mock_sstable_store();
+        let read_options = Arc::new(SstableIteratorReadOptions::default());
+        let cache = create_small_table_cache();
+
+        // Three non-overlapping sources, each contributing a disjoint slice of keys, so we know
+        // exactly which source every emitted key must have come from.
+        let mut tables = vec![];
+        for (object_id, idx_range) in [(0, 0..3), (1, 3..6), (2, 6..9)] {
+            let table = gen_iterator_test_sstable_from_kv_pair(
+                object_id,
+                idx_range
+                    .map(|i| (i, 233, HummockValue::put(iterator_test_value_of(i))))
+                    .collect(),
+                sstable_store.clone(),
+            )
+            .await;
+            tables.push(cache.insert(object_id, object_id, 1, Box::new(table), CachePriority::High));
+        }
+
+        let iters = tables
+            .into_iter()
+            .map(|handle| {
+                SstableIterator::create(handle, sstable_store.clone(), read_options.clone())
+            })
+            .collect_vec();
+        let mut merge_iter = OrderedMergeIteratorInner::new(iters);
+        merge_iter.rewind().await.unwrap();
+
+        let mut expected_source = 0;
+        let mut count = 0;
+        while merge_iter.is_valid() {
+            assert_eq!(merge_iter.key(), iterator_test_key_of(count).to_ref());
+            assert_eq!(merge_iter.current_source_index(), expected_source);
+            count += 1;
+            if count % 3 == 0 {
+                expected_source += 1;
+            }
+            merge_iter.next().await.unwrap();
+        }
+        assert_eq!(count, 9);
+    }
+
     struct CancellationTestIterator {}
 
     impl HummockIterator for CancellationTestIterator {
@@ -392,4 +440,489 @@ mod test {
         // Dropping the future will panic if the OrderedMergeIteratorInner is not cancellation safe.
         // See https://github.com/risingwavelabs/risingwave/issues/6637
     }
+
+    /// An iterator whose `rewind`/`seek` sleeps before becoming valid, for asserting that a
+    /// `MergeIteratorInner::seek` dropped mid-flight leaves every child in a state where a
+    /// subsequent `rewind` still produces correct results.
+    struct SlowThenValidTestIterator {
+        key: FullKey<Vec<u8>>,
+        valid: bool,
+        delay: Duration,
+    }
+
+    impl HummockIterator for SlowThenValidTestIterator {
+        type Direction = Forward;
+
+        type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+        type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+        type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+        fn next(&mut self) -> Self::NextFuture<'_> {
+            async move {
+                self.valid = false;
+                Ok(())
+            }
+        }
+
+        fn key(&self) -> FullKey<&[u8]> {
+            self.key.to_ref()
+        }
+
+        fn value(&self) -> HummockValue<&[u8]> {
+            HummockValue::put(b"v")
+        }
+
+        fn is_valid(&self) -> bool {
+            self.valid
+        }
+
+        fn rewind(&mut self) -> Self::RewindFuture<'_> {
+            async move {
+                tokio::time::sleep(self.delay).await;
+                self.valid = true;
+                Ok(())
+            }
+        }
+
+        fn seek<'a>(&'a mut self, _key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+            async move {
+                tokio::time::sleep(self.delay).await;
+                self.valid = true;
+                Ok(())
+            }
+        }
+
+        fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic) {}
+    }
+
+    #[tokio::test]
+    async fn test_merge_iter_seek_cancel_then_rewind() {
+        let mut merge_iter = UnorderedMergeIteratorInner::new(vec![
+            SlowThenValidTestIterator {
+                key: iterator_test_key_of(0),
+                valid: false,
+                delay: Duration::from_millis(600),
+            },
+            SlowThenValidTestIterator {
+                key: iterator_test_key_of(1),
+                valid: false,
+                delay: Duration::from_millis(600),
+            },
+        ]);
+
+        {
+            let future = merge_iter.seek(iterator_test_key_of(0).to_ref());
+            pin_mut!(future);
+            // Drive the seek partway, then drop it before the slow children finish.
+            assert!(poll_fn(|cx| { Poll::Ready(future.poll_unpin(cx)) })
+                .await
+                .is_pending());
+        }
+
+        // A dropped seek must leave the iterator consistently re-seekable: a subsequent rewind
+        // should still bring up every child and yield every key, not panic or hang.
+        merge_iter.rewind().await.unwrap();
+        let mut count = 0;
+        while merge_iter.is_valid() {
+            count += 1;
+            merge_iter.next().await.unwrap();
+        }
+        assert_eq!(count, 2);
+    }
+
+    /// An iterator whose `rewind`/`seek` records how many instances are concurrently in flight,
+    /// for asserting the fan-out bound of [`UnorderedMergeIteratorInner::new_with_max_concurrent_seeks`].
+    struct ConcurrentSeekTrackingIterator {
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl ConcurrentSeekTrackingIterator {
+        async fn track(&self) -> HummockResult<()> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            // Yield a few times so other buffered children get a chance to start while this one
+            // is still "in flight".
+            tokio::task::yield_now().await;
+            tokio::task::yield_now().await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    impl HummockIterator for ConcurrentSeekTrackingIterator {
+        type Direction = Forward;
+
+        type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+        type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+        type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+        fn next(&mut self) -> Self::NextFuture<'_> {
+            async { Ok(()) }
+        }
+
+        fn key(&self) -> FullKey<&[u8]> {
+            FullKey {
+                user_key: UserKey {
+                    table_id: Default::default(),
+                    table_key: TableKey(&b"test_key"[..]),
+                },
+                epoch: 0,
+            }
+        }
+
+        fn value(&self) -> HummockValue<&[u8]> {
+            HummockValue::delete()
+        }
+
+        fn is_valid(&self) -> bool {
+            false
+        }
+
+        fn rewind(&mut self) -> Self::RewindFuture<'_> {
+            self.track()
+        }
+
+        fn seek<'a>(&'a mut self, _key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+            self.track()
+        }
+
+        fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic) {}
+    }
+
+    #[tokio::test]
+    async fn test_merge_iter_bounded_concurrent_seeks() {
+        const MAX_CONCURRENT_SEEKS: usize = 2;
+        const CHILDREN_COUNT: usize = 10;
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let children = (0..CHILDREN_COUNT).map(|_| ConcurrentSeekTrackingIterator {
+            in_flight: in_flight.clone(),
+            max_in_flight: max_in_flight.clone(),
+        });
+        let mut merge_iter = UnorderedMergeIteratorInner::new_with_max_concurrent_seeks(
+            children,
+            MAX_CONCURRENT_SEEKS,
+        );
+
+        merge_iter.rewind().await.unwrap();
+
+        assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+        assert!(max_in_flight.load(Ordering::SeqCst) > 0);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= MAX_CONCURRENT_SEEKS);
+    }
+
+    #[tokio::test]
+    async fn test_merge_iter_with_tight_memory_limiter() {
+        let iters = gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 10).await;
+        // A tight budget that only admits a couple of children's blocks at once, so iteration
+        // proceeds in several waves of seeks rather than all ten children loading at once.
+        let memory_limiter = MemoryLimiter::new(2 * default_builder_opt_for_test().block_capacity as u64);
+        let mut merge_iter = UnorderedMergeIteratorInner::new_with_memory_limiter(
+            iters,
+            &memory_limiter,
+            default_builder_opt_for_test().block_capacity as u64,
+        );
+        merge_iter.rewind().await.unwrap();
+
+        let mut count = 0;
+        while merge_iter.is_valid() {
+            count += 1;
+            merge_iter.next().await.unwrap();
+        }
+        assert_eq!(count, TEST_KEYS_COUNT * 10);
+    }
+
+    #[tokio::test]
+    async fn test_merge_iter_resume_from_frontier_keys() {
+        let total_count = TEST_KEYS_COUNT * 3;
+        let halfway = total_count / 2;
+
+        // Scan halfway through, then snapshot the position of every child.
+        let mut merge_iter = UnorderedMergeIteratorInner::new(
+            gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
+        );
+        merge_iter.rewind().await.unwrap();
+        for _ in 0..halfway {
+            assert!(merge_iter.is_valid());
+            merge_iter.next().await.unwrap();
+        }
+        let frontier_keys = merge_iter.export_frontier_keys();
+
+        // Reconstruct from the snapshot and collect the remaining keys.
+        let resumed_iters = gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await;
+        let mut resumed_iter =
+            UnorderedMergeIteratorInner::new_from_frontier_keys(resumed_iters, frontier_keys)
+                .await
+                .unwrap();
+        let mut resumed_keys = vec![];
+        while resumed_iter.is_valid() {
+            resumed_keys.push(resumed_iter.key().encode());
+            resumed_iter.next().await.unwrap();
+        }
+
+        // Collect the remaining keys of an uninterrupted scan for comparison.
+        let mut uninterrupted_keys = vec![];
+        for i in halfway..total_count {
+            uninterrupted_keys.push(iterator_test_key_of(i).encode());
+        }
+
+        assert_eq!(resumed_keys, uninterrupted_keys);
+    }
+
+    #[tokio::test]
+    async fn test_merge_iterator_ordered_vs_unordered_tie_break() {
+        let sstable_store = mock_sstable_store();
+        let cache = create_small_table_cache();
+
+        // Two tables sharing the exact same (key, epoch) pairs, so every key is a tie. Values
+        // are distinguishable so we can tell which child's duplicate won.
+        let mut handles = vec![];
+        for (object_id, tag) in [(0, "first"), (1, "second")] {
+            let table = gen_iterator_test_sstable_from_kv_pair(
+                object_id,
+                (0..TEST_KEYS_COUNT)
+                    .map(|i| (i, 233, HummockValue::put(tag.as_bytes().to_vec())))
+                    .collect(),
+                sstable_store.clone(),
+            )
+            .await;
+            handles.push(cache.insert(object_id, object_id, 1, Box::new(table), CachePriority::High));
+        }
+        let make_iters = |handles: &[_]| {
+            handles
+                .iter()
+                .map(|handle: &crate::hummock::TableHolder| {
+                    SstableIterator::create(
+                        handle.clone(),
+                        sstable_store.clone(),
+                        Arc::new(SstableIteratorReadOptions::default()),
+                    )
+                })
+                .collect_vec()
+        };
+
+        // Ordered mode collapses each tie into a single item, deterministically keeping the
+        // first input's value.
+        let mut ordered = MergeIterator::new(make_iters(&handles), true);
+        ordered.rewind().await.unwrap();
+        let mut ordered_count = 0;
+        while ordered.is_valid() {
+            assert_eq!(ordered.value().into_user_value().unwrap(), "first".as_bytes());
+            ordered_count += 1;
+            ordered.next().await.unwrap();
+        }
+        assert_eq!(ordered_count, TEST_KEYS_COUNT);
+
+        // Unordered mode does not collapse ties: both children's copies of each key surface.
+        let mut unordered = MergeIterator::new(make_iters(&handles), false);
+        unordered.rewind().await.unwrap();
+        let mut unordered_count = 0;
+        while unordered.is_valid() {
+            unordered_count += 1;
+            unordered.next().await.unwrap();
+        }
+        assert_eq!(unordered_count, TEST_KEYS_COUNT * 2);
+    }
+
+    /// An iterator whose `seek`/`rewind` sleeps for a fixed duration, for asserting that the
+    /// per-child seek timing stats reflect the slowest child rather than some aggregate across
+    /// all children.
+    struct SlowSeekTestIterator {
+        delay: Duration,
+    }
+
+    impl HummockIterator for SlowSeekTestIterator {
+        type Direction = Forward;
+
+        type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+        type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+        type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+        fn next(&mut self) -> Self::NextFuture<'_> {
+            async { Ok(()) }
+        }
+
+        fn key(&self) -> FullKey<&[u8]> {
+            FullKey {
+                user_key: UserKey {
+                    table_id: Default::default(),
+                    table_key: TableKey(&b"test_key"[..]),
+                },
+                epoch: 0,
+            }
+        }
+
+        fn value(&self) -> HummockValue<&[u8]> {
+            HummockValue::delete()
+        }
+
+        fn is_valid(&self) -> bool {
+            false
+        }
+
+        fn rewind(&mut self) -> Self::RewindFuture<'_> {
+            async move {
+                tokio::time::sleep(self.delay).await;
+                Ok(())
+            }
+        }
+
+        fn seek<'a>(&'a mut self, _key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+            async move {
+                tokio::time::sleep(self.delay).await;
+                Ok(())
+            }
+        }
+
+        fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic) {}
+    }
+
+    #[tokio::test]
+    async fn test_merge_iter_max_child_seek_duration_stat() {
+        // Comfortably above MERGE_ITER_SLOW_CHILD_SEEK_THRESHOLD (500ms), so it is also counted as
+        // a slow child, while the other child stays well below it.
+        let slow_delay = Duration::from_millis(600);
+        let fast_delay = Duration::from_millis(1);
+
+        let mut merge_iter = UnorderedMergeIteratorInner::new(vec![
+            SlowSeekTestIterator { delay: slow_delay },
+            SlowSeekTestIterator { delay: fast_delay },
+        ]);
+        merge_iter.rewind().await.unwrap();
+
+        let mut stats = StoreLocalStatistic::default();
+        merge_iter.collect_local_statistic(&mut stats);
+
+        // The recorded max reflects the slow child, not the fast one or some aggregate of both.
+        assert!(stats.merge_iter_max_child_seek_duration_ns >= slow_delay.as_nanos() as u64);
+        assert!(stats.merge_iter_max_child_seek_duration_ns < Duration::from_secs(2).as_nanos() as u64);
+        assert_eq!(stats.merge_iter_slow_child_seek_count, 1);
+
+        stats.ignore();
+    }
+
+    #[tokio::test]
+    async fn test_merge_iter_seek_owned() {
+        let mut seek_iter = UnorderedMergeIteratorInner::new(
+            gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
+        );
+        let mut seek_owned_iter = UnorderedMergeIteratorInner::new(
+            gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
+        );
+
+        seek_iter
+            .seek(iterator_test_key_of(TEST_KEYS_COUNT + 7).to_ref())
+            .await
+            .unwrap();
+        // A freshly-allocated key, so its lifetime isn't tied to anything in scope.
+        seek_owned_iter
+            .seek_owned(iterator_test_key_of(TEST_KEYS_COUNT + 7))
+            .await
+            .unwrap();
+
+        assert_eq!(seek_iter.key(), seek_owned_iter.key());
+        assert_eq!(seek_iter.value(), seek_owned_iter.value());
+    }
+
+    async fn build_tied_sstable_iters() -> Vec<SstableIterator> {
+        let sstable_store = mock_sstable_store();
+        let cache = create_small_table_cache();
+        let mut result = vec![];
+        for (object_id, value) in [(0, b"first".to_vec()), (1, b"second".to_vec())] {
+            let table = gen_iterator_test_sstable_from_kv_pair(
+                object_id,
+                vec![(0, 100, HummockValue::put(value))],
+                sstable_store.clone(),
+            )
+            .await;
+            let handle = cache.insert(table.id, table.id, 1, Box::new(table), CachePriority::High);
+            result.push(SstableIterator::create(
+                handle,
+                sstable_store.clone(),
+                Arc::new(SstableIteratorReadOptions::default()),
+            ));
+        }
+        result
+    }
+
+    #[tokio::test]
+    async fn test_ordered_merge_tie_break() {
+        use crate::hummock::iterator::OrderedMergeTieBreak;
+
+        let mut prefer_first = OrderedMergeIteratorInner::new(build_tied_sstable_iters().await);
+        prefer_first.rewind().await.unwrap();
+        assert_eq!(
+            prefer_first.value().into_user_value().unwrap(),
+            b"first".as_slice()
+        );
+
+        let mut prefer_last = OrderedMergeIteratorInner::new_with_tie_break(
+            build_tied_sstable_iters().await,
+            OrderedMergeTieBreak::PreferLast,
+        );
+        prefer_last.rewind().await.unwrap();
+        assert_eq!(
+            prefer_last.value().into_user_value().unwrap(),
+            b"second".as_slice()
+        );
+    }
+
+    async fn build_same_user_key_different_epoch_sstable_iters(
+        // (object_id, epoch, value), in the order they are passed to the merge iterator.
+        sources: &[(u64, u64, &[u8])],
+    ) -> Vec<SstableIterator> {
+        let sstable_store = mock_sstable_store();
+        let cache = create_small_table_cache();
+        let mut result = vec![];
+        for &(object_id, epoch, value) in sources {
+            let table = gen_iterator_test_sstable_from_kv_pair(
+                object_id,
+                vec![(0, epoch, HummockValue::put(value.to_vec()))],
+                sstable_store.clone(),
+            )
+            .await;
+            let handle = cache.insert(table.id, table.id, 1, Box::new(table), CachePriority::High);
+            result.push(SstableIterator::create(
+                handle,
+                sstable_store.clone(),
+                Arc::new(SstableIteratorReadOptions::default()),
+            ));
+        }
+        result
+    }
+
+    #[tokio::test]
+    async fn test_ordered_merge_epoch_precedes_source_order() {
+        use crate::hummock::iterator::OrderedMergeTieBreak;
+
+        // Same user key at two different epochs. The lower-epoch source is passed first, so a
+        // tie-break that only looked at source order would surface it first; but the higher
+        // epoch must win regardless, since `FullKey`'s ordering already compares epoch before
+        // `OrderedMergeIteratorInner` ever falls back to source index.
+        let sources: [(u64, u64, &[u8]); 2] = [(0, 100, b"older"), (1, 200, b"newer")];
+
+        let mut prefer_first = OrderedMergeIteratorInner::new(
+            build_same_user_key_different_epoch_sstable_iters(&sources).await,
+        );
+        prefer_first.rewind().await.unwrap();
+        assert_eq!(
+            prefer_first.value().into_user_value().unwrap(),
+            b"newer".as_slice()
+        );
+
+        // Even when the tie-break is explicitly set to prefer the other source order, the higher
+        // epoch still wins, because the two sources are not actually tied: their epochs differ.
+        let mut prefer_last = OrderedMergeIteratorInner::new_with_tie_break(
+            build_same_user_key_different_epoch_sstable_iters(&sources).await,
+            OrderedMergeTieBreak::PreferLast,
+        );
+        prefer_last.rewind().await.unwrap();
+        assert_eq!(
+            prefer_last.value().into_user_value().unwrap(),
+            b"newer".as_slice()
+        );
+    }
 }
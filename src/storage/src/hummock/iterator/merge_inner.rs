@@ -16,16 +16,18 @@ use std::collections::binary_heap::PeekMut;
 use std::collections::{BinaryHeap, LinkedList};
 use std::future::Future;
 use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
 
 use bytes::Bytes;
+use futures::{stream, StreamExt, TryStreamExt};
 use risingwave_hummock_sdk::key::{FullKey, TableKey, UserKey};
 use risingwave_hummock_sdk::HummockEpoch;
 
 use crate::hummock::iterator::{DirectionEnum, Forward, HummockIterator, HummockIteratorDirection};
 use crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatchIterator;
 use crate::hummock::value::HummockValue;
-use crate::hummock::HummockResult;
-use crate::monitor::StoreLocalStatistic;
+use crate::hummock::{HummockResult, MemoryLimiter};
+use crate::monitor::{StoreLocalStatistic, MERGE_ITER_SLOW_CHILD_SEEK_THRESHOLD};
 
 pub trait NodeExtraOrderInfo: Eq + Ord + Send + Sync {}
 
@@ -39,6 +41,12 @@ impl NodeExtraOrderInfo for OrderedNodeExtra {}
 pub struct Node<I: HummockIterator, T: NodeExtraOrderInfo> {
     iter: I,
     extra_order_info: T,
+
+    /// Position of this child among all children passed to [`MergeIteratorInner`]'s
+    /// constructor, stable for the lifetime of the merge iterator regardless of which of
+    /// `heap`/`unused_iters` the node currently lives in. Used to align
+    /// [`MergeIteratorInner::export_frontier_keys`] with the original iterator order.
+    idx: usize,
 }
 
 impl<I: HummockIterator, T: NodeExtraOrderInfo> Eq for Node<I, T> where Self: PartialEq {}
@@ -67,7 +75,10 @@ impl<I: HummockIterator> PartialOrd for Node<I, UnorderedNodeExtra> {
 /// Implement `PartialOrd` for ordered iter node. Compare key and use order index as tie breaker.
 impl<I: HummockIterator> PartialOrd for Node<I, OrderedNodeExtra> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        // The `extra_info` is used as a tie-breaker when the keys are equal.
+        // `iter.key()` is a `FullKey`, whose own `Ord` impl already orders by user key and then
+        // by epoch (higher epoch first). So two children only ever reach `extra_order_info` — the
+        // source-order tie-break — when they agree on both user key and epoch, i.e. are true
+        // duplicates of the same versioned key.
         Some(match I::Direction::direction() {
             DirectionEnum::Forward => other
                 .iter
@@ -104,6 +115,30 @@ pub struct MergeIteratorInner<I: HummockIterator, NE: NodeExtraOrderInfo> {
     heap: BinaryHeap<Node<I, NE>>,
 
     last_table_key: Vec<u8>,
+
+    /// Maximum number of children that may have a `seek`/`rewind` in flight at once. Bounds the
+    /// fan-out of concurrent object-store requests when there are many children, e.g. hundreds of
+    /// L0 iterators. `usize::MAX` (the default) effectively disables the bound.
+    max_concurrent_seeks: usize,
+
+    /// Longest single-child `seek`/`rewind` latency observed so far, across all calls made on
+    /// this iterator.
+    max_child_seek_duration: Duration,
+    /// Number of child `seek`/`rewind` calls, across all calls made on this iterator, whose
+    /// latency exceeded [`MERGE_ITER_SLOW_CHILD_SEEK_THRESHOLD`].
+    slow_child_seek_count: u64,
+}
+
+/// Which source wins when two children of an [`OrderedMergeIteratorInner`] produce an equal key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum OrderedMergeTieBreak {
+    /// The source passed first to the constructor wins. This is the default and preserves the
+    /// iterator's original behavior.
+    #[default]
+    PreferFirst,
+    /// The source passed last to the constructor wins, e.g. when later entries represent newer
+    /// deltas that should shadow earlier ones on an exact key collision.
+    PreferLast,
 }
 
 /// An order aware merge iterator.
@@ -112,25 +147,43 @@ pub type OrderedMergeIteratorInner<I: HummockIterator> = MergeIteratorInner<I, O
 
 impl<I: HummockIterator> OrderedMergeIteratorInner<I> {
     pub fn new(iterators: impl IntoIterator<Item = I>) -> Self {
-        Self::create(iterators)
+        Self::create(iterators, OrderedMergeTieBreak::default())
     }
 
     pub fn for_compactor(iterators: impl IntoIterator<Item = I>) -> Self {
-        Self::create(iterators)
+        Self::create(iterators, OrderedMergeTieBreak::default())
     }
 
-    fn create(iterators: impl IntoIterator<Item = I>) -> Self {
+    /// Like [`Self::new`], but lets the caller choose which source wins when two children
+    /// produce an equal key, instead of always preferring the one passed first.
+    pub fn new_with_tie_break(
+        iterators: impl IntoIterator<Item = I>,
+        tie_break: OrderedMergeTieBreak,
+    ) -> Self {
+        Self::create(iterators, tie_break)
+    }
+
+    fn create(iterators: impl IntoIterator<Item = I>, tie_break: OrderedMergeTieBreak) -> Self {
+        let iterators: Vec<I> = iterators.into_iter().collect();
+        let count = iterators.len();
         Self {
             unused_iters: iterators
                 .into_iter()
                 .enumerate()
                 .map(|(i, iter)| Node {
                     iter,
-                    extra_order_info: i,
+                    extra_order_info: match tie_break {
+                        OrderedMergeTieBreak::PreferFirst => i,
+                        OrderedMergeTieBreak::PreferLast => count - 1 - i,
+                    },
+                    idx: i,
                 })
                 .collect(),
             heap: BinaryHeap::new(),
             last_table_key: Vec::new(),
+            max_concurrent_seeks: usize::MAX,
+            max_child_seek_duration: Duration::ZERO,
+            slow_child_seek_count: 0,
         }
     }
 }
@@ -156,6 +209,33 @@ impl<I: HummockIterator, NE: NodeExtraOrderInfo> MergeIteratorInner<I, NE> {
         for node in &self.unused_iters {
             node.iter.collect_local_statistic(stats);
         }
+        stats.merge_iter_max_child_seek_duration_ns = stats
+            .merge_iter_max_child_seek_duration_ns
+            .max(self.max_child_seek_duration.as_nanos() as u64);
+        stats.merge_iter_slow_child_seek_count += self.slow_child_seek_count;
+    }
+
+    /// Folds the per-child durations of one batched `seek`/`rewind` call into this iterator's
+    /// running max/slow-count, so they can later be surfaced via
+    /// [`Self::collect_local_statistic_impl`].
+    fn record_child_seek_durations(&mut self, durations: &[Duration]) {
+        if let Some(max) = durations.iter().max() {
+            self.max_child_seek_duration = self.max_child_seek_duration.max(*max);
+        }
+        self.slow_child_seek_count += durations
+            .iter()
+            .filter(|duration| **duration >= MERGE_ITER_SLOW_CHILD_SEEK_THRESHOLD)
+            .count() as u64;
+    }
+
+    /// The position, among the iterators originally passed to the constructor, that the
+    /// currently emitted key came from. Meant for diagnostics that need to attribute each merged
+    /// key to its source SST/level.
+    ///
+    /// # Panics
+    /// Panics if the iterator is invalid (no child at the top of the heap).
+    pub fn current_source_index(&self) -> usize {
+        self.heap.peek().expect("no inner iter").idx
     }
 }
 
@@ -172,19 +252,87 @@ impl<I: HummockIterator> UnorderedMergeIteratorInner<I> {
         Self::create(iterators)
     }
 
+    /// Like [`Self::new`], but bounds the number of children that may have a `seek`/`rewind` in
+    /// flight at once to `max_concurrent_seeks`, so seeks over many children proceed in waves
+    /// instead of all at once.
+    pub fn new_with_max_concurrent_seeks(
+        iterators: impl IntoIterator<Item = I>,
+        max_concurrent_seeks: usize,
+    ) -> Self {
+        let mut iter = Self::create(iterators);
+        iter.max_concurrent_seeks = max_concurrent_seeks;
+        iter
+    }
+
+    /// Like [`Self::new_with_max_concurrent_seeks`], but derives `max_concurrent_seeks` from
+    /// `memory_limiter`'s configured quota instead of taking it directly: at most
+    /// `memory_limiter.quota() / bytes_per_child` children have a `seek`/`rewind` in flight at
+    /// once, which keeps concurrent block-decoding work roughly proportional to the limiter's
+    /// budget. `bytes_per_child` should be a rough per-child block-memory estimate (e.g. the
+    /// configured SST block capacity).
+    ///
+    /// Note this only reads `memory_limiter.quota()` once, as a sizing hint for the cap above —
+    /// it does not call `require_memory`/hold a `MemoryTracker`, so it never actually reserves or
+    /// releases memory with `memory_limiter`, and it doesn't evict an already-loaded child's
+    /// blocks or reload them on demand when that child falls behind the merge frontier. Children
+    /// beyond the cap simply wait their turn to start a seek, the same as
+    /// [`Self::new_with_max_concurrent_seeks`].
+    pub fn new_with_memory_limiter(
+        iterators: impl IntoIterator<Item = I>,
+        memory_limiter: &MemoryLimiter,
+        bytes_per_child: u64,
+    ) -> Self {
+        let max_concurrent_seeks =
+            std::cmp::max(1, memory_limiter.quota() / bytes_per_child.max(1)) as usize;
+        Self::new_with_max_concurrent_seeks(iterators, max_concurrent_seeks)
+    }
+
     fn create(iterators: impl IntoIterator<Item = I>) -> Self {
         Self {
             unused_iters: iterators
                 .into_iter()
-                .map(|iter| Node {
+                .enumerate()
+                .map(|(idx, iter)| Node {
                     iter,
                     extra_order_info: (),
+                    idx,
                 })
                 .collect(),
             heap: BinaryHeap::new(),
             last_table_key: Vec::new(),
+            max_concurrent_seeks: usize::MAX,
+            max_child_seek_duration: Duration::ZERO,
+            slow_child_seek_count: 0,
         }
     }
+
+    /// Like [`Self::new`], but resumes each child from a previously exported frontier key
+    /// instead of rewinding/seeking it from scratch: children whose saved key is non-empty are
+    /// sought to that key in one batched pass (bounded by `max_concurrent_seeks`), while children
+    /// with an empty saved key (already exhausted when the frontier was captured) are left
+    /// unsought and therefore excluded from the resumed scan, exactly as they would have been had
+    /// the scan never paused. Pairs with [`MergeIteratorInner::export_frontier_keys`] to
+    /// checkpoint and resume a long scan, e.g. across a process restart.
+    pub async fn new_from_frontier_keys(
+        iterators: impl IntoIterator<Item = I>,
+        frontier_keys: Vec<Vec<u8>>,
+    ) -> HummockResult<Self> {
+        let mut iter = Self::create(iterators);
+        let max_concurrent_seeks = iter.max_concurrent_seeks;
+        stream::iter(iter.unused_iters.iter_mut().filter_map(|node| {
+            let key = frontier_keys.get(node.idx)?;
+            if key.is_empty() {
+                None
+            } else {
+                Some(node.iter.seek(FullKey::decode(key)))
+            }
+        }))
+        .buffer_unordered(max_concurrent_seeks)
+        .try_collect::<Vec<_>>()
+        .await?;
+        iter.build_heap();
+        Ok(iter)
+    }
 }
 
 impl<I: HummockIterator, NE: NodeExtraOrderInfo> MergeIteratorInner<I, NE>
@@ -206,6 +354,17 @@ where
             .drain_filter(|i| i.iter.is_valid())
             .collect();
     }
+
+    /// Exports the current key of each child, indexed by the child's position among the
+    /// iterators originally passed to the constructor. A child that is not currently valid
+    /// (exhausted, or never sought/rewound) is represented by an empty `Vec<u8>`.
+    pub fn export_frontier_keys(&self) -> Vec<Vec<u8>> {
+        let mut frontier_keys = vec![Vec::new(); self.heap.len() + self.unused_iters.len()];
+        for node in self.heap.iter() {
+            frontier_keys[node.idx] = node.iter.key().encode();
+        }
+        frontier_keys
+    }
 }
 
 /// The behaviour of `next` of order aware merge iterator is different from the normal one, so we
@@ -400,20 +559,47 @@ where
     }
 
     fn rewind(&mut self) -> Self::RewindFuture<'_> {
+        // Built from `buffer_unordered`/`try_collect` rather than a spawned task per child, so
+        // dropping this future (e.g. on query cancellation) drops every outstanding child rewind
+        // in place instead of letting them run to completion in the background. Every child is
+        // independently re-seekable regardless of how far it got, so a dropped rewind can always
+        // be safely retried from scratch.
         async move {
             self.reset_heap();
-            futures::future::try_join_all(self.unused_iters.iter_mut().map(|x| x.iter.rewind()))
-                .await?;
+            let max_concurrent_seeks = self.max_concurrent_seeks;
+            let durations: Vec<Duration> = stream::iter(self.unused_iters.iter_mut().map(|x| {
+                async move {
+                    let start = Instant::now();
+                    x.iter.rewind().await?;
+                    Ok(start.elapsed())
+                }
+            }))
+            .buffer_unordered(max_concurrent_seeks)
+            .try_collect()
+            .await?;
+            self.record_child_seek_durations(&durations);
             self.build_heap();
             Ok(())
         }
     }
 
     fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+        // See the cancellation note on `rewind` above: no child seek is spawned onto a separate
+        // task, so dropping this future promptly drops every outstanding child seek too.
         async move {
             self.reset_heap();
-            futures::future::try_join_all(self.unused_iters.iter_mut().map(|x| x.iter.seek(key)))
-                .await?;
+            let max_concurrent_seeks = self.max_concurrent_seeks;
+            let durations: Vec<Duration> = stream::iter(self.unused_iters.iter_mut().map(|x| {
+                async move {
+                    let start = Instant::now();
+                    x.iter.seek(key).await?;
+                    Ok(start.elapsed())
+                }
+            }))
+            .buffer_unordered(max_concurrent_seeks)
+            .try_collect()
+            .await?;
+            self.record_child_seek_durations(&durations);
             self.build_heap();
             Ok(())
         }
@@ -423,3 +609,87 @@ where
         self.collect_local_statistic_impl(stats);
     }
 }
+
+/// Wraps either an [`OrderedMergeIteratorInner`] or an [`UnorderedMergeIteratorInner`], so that
+/// call sites which decide at runtime whether their inputs may contain duplicate keys (and
+/// therefore need the deterministic tie-break that ordering provides) don't have to commit to one
+/// variant at compile time.
+pub enum MergeIterator<I: HummockIterator> {
+    Ordered(OrderedMergeIteratorInner<I>),
+    Unordered(UnorderedMergeIteratorInner<I>),
+}
+
+impl<I: HummockIterator> MergeIterator<I> {
+    /// Creates an ordered merge iterator (ties broken by input order) when `ordered` is `true`,
+    /// or an unordered one otherwise.
+    pub fn new(iterators: impl IntoIterator<Item = I>, ordered: bool) -> Self {
+        if ordered {
+            MergeIterator::Ordered(OrderedMergeIteratorInner::new(iterators))
+        } else {
+            MergeIterator::Unordered(UnorderedMergeIteratorInner::new(iterators))
+        }
+    }
+}
+
+impl<I: HummockIterator> HummockIterator for MergeIterator<I> {
+    type Direction = I::Direction;
+
+    type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+    fn next(&mut self) -> Self::NextFuture<'_> {
+        async move {
+            match self {
+                MergeIterator::Ordered(iter) => iter.next().await,
+                MergeIterator::Unordered(iter) => iter.next().await,
+            }
+        }
+    }
+
+    fn key(&self) -> FullKey<&[u8]> {
+        match self {
+            MergeIterator::Ordered(iter) => iter.key(),
+            MergeIterator::Unordered(iter) => iter.key(),
+        }
+    }
+
+    fn value(&self) -> HummockValue<&[u8]> {
+        match self {
+            MergeIterator::Ordered(iter) => iter.value(),
+            MergeIterator::Unordered(iter) => iter.value(),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            MergeIterator::Ordered(iter) => iter.is_valid(),
+            MergeIterator::Unordered(iter) => iter.is_valid(),
+        }
+    }
+
+    fn rewind(&mut self) -> Self::RewindFuture<'_> {
+        async move {
+            match self {
+                MergeIterator::Ordered(iter) => iter.rewind().await,
+                MergeIterator::Unordered(iter) => iter.rewind().await,
+            }
+        }
+    }
+
+    fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+        async move {
+            match self {
+                MergeIterator::Ordered(iter) => iter.seek(key).await,
+                MergeIterator::Unordered(iter) => iter.seek(key).await,
+            }
+        }
+    }
+
+    fn collect_local_statistic(&self, stats: &mut StoreLocalStatistic) {
+        match self {
+            MergeIterator::Ordered(iter) => iter.collect_local_statistic(stats),
+            MergeIterator::Unordered(iter) => iter.collect_local_statistic(stats),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+
+use bytes::Bytes;
+use risingwave_hummock_sdk::key::FullKey;
+
+use super::HummockIterator;
+use crate::hummock::{HummockResult, HummockValue};
+use crate::monitor::StoreLocalStatistic;
+
+/// Transparently wraps a `HummockIterator`, recording the first and last keys observed between a
+/// `rewind`/`seek` and the next one. Meant for building index metadata (e.g. a block or SST's key
+/// span) in the same pass that already scans the data, instead of paying for a second scan just
+/// to learn the endpoints of a filtered stream.
+pub struct MinMaxTrackingIterator<I: HummockIterator> {
+    inner: I,
+    /// The first key observed this pass, i.e. right after the last `rewind`/`seek`.
+    first_key: Option<Vec<u8>>,
+    /// The most recently observed key this pass.
+    last_key: Option<Vec<u8>>,
+}
+
+impl<I: HummockIterator> MinMaxTrackingIterator<I> {
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            first_key: None,
+            last_key: None,
+        }
+    }
+
+    /// The smallest key observed since the last `rewind`/`seek`, or `None` if the stream since
+    /// then was empty. Computed from the first/last observed keys via `Ord` rather than the
+    /// iteration direction, so it is correct for both forward and backward iterators.
+    pub fn min_key(&self) -> Option<FullKey<&[u8]>> {
+        self.endpoints().map(|(min, _)| min)
+    }
+
+    /// The largest key observed since the last `rewind`/`seek`, or `None` if the stream since
+    /// then was empty.
+    pub fn max_key(&self) -> Option<FullKey<&[u8]>> {
+        self.endpoints().map(|(_, max)| max)
+    }
+
+    fn endpoints(&self) -> Option<(FullKey<&[u8]>, FullKey<&[u8]>)> {
+        let first = FullKey::decode(self.first_key.as_deref()?);
+        let last = FullKey::decode(self.last_key.as_deref()?);
+        Some(if first <= last {
+            (first, last)
+        } else {
+            (last, first)
+        })
+    }
+
+    fn reset(&mut self) {
+        self.first_key = None;
+        self.last_key = None;
+        self.track_current();
+    }
+
+    fn track_current(&mut self) {
+        if !self.inner.is_valid() {
+            return;
+        }
+        let key = self.inner.key().encode();
+        if self.first_key.is_none() {
+            self.first_key = Some(key.clone());
+        }
+        self.last_key = Some(key);
+    }
+}
+
+impl<I: HummockIterator> HummockIterator for MinMaxTrackingIterator<I> {
+    type Direction = I::Direction;
+
+    type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+    fn next(&mut self) -> Self::NextFuture<'_> {
+        async move {
+            self.inner.next().await?;
+            self.track_current();
+            Ok(())
+        }
+    }
+
+    fn key(&self) -> FullKey<&[u8]> {
+        self.inner.key()
+    }
+
+    fn value(&self) -> HummockValue<&[u8]> {
+        self.inner.value()
+    }
+
+    fn value_len(&self) -> usize {
+        self.inner.value_len()
+    }
+
+    fn value_owned(&self) -> HummockValue<Bytes> {
+        self.inner.value_owned()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.inner.is_valid()
+    }
+
+    fn rewind(&mut self) -> Self::RewindFuture<'_> {
+        async move {
+            self.inner.rewind().await?;
+            self.reset();
+            Ok(())
+        }
+    }
+
+    fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+        async move {
+            self.inner.seek(key).await?;
+            self.reset();
+            Ok(())
+        }
+    }
+
+    fn collect_local_statistic(&self, stats: &mut StoreLocalStatistic) {
+        self.inner.collect_local_statistic(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::{
+        default_builder_opt_for_test, gen_iterator_test_sstable_base, iterator_test_key_of,
+        mock_sstable_store, TEST_KEYS_COUNT,
+    };
+    use crate::hummock::iterator::ConcatIterator;
+    use crate::hummock::sstable::SstableIteratorReadOptions;
+
+    #[tokio::test]
+    async fn test_min_max_tracking_empty() {
+        let sstable_store = mock_sstable_store();
+        let table0 = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let concat_iter = ConcatIterator::new(
+            vec![table0.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        let mut iter = MinMaxTrackingIterator::new(concat_iter);
+
+        // Seeking past the end of the stream leaves it empty.
+        iter.seek(iterator_test_key_of(TEST_KEYS_COUNT).to_ref())
+            .await
+            .unwrap();
+        assert!(!iter.is_valid());
+        assert!(iter.min_key().is_none());
+        assert!(iter.max_key().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_min_max_tracking_filtered_concat() {
+        let sstable_store = mock_sstable_store();
+        let table0 = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let table1 = gen_iterator_test_sstable_base(
+            1,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let concat_iter = ConcatIterator::new(
+            vec![table0.get_sstable_info(), table1.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        let mut iter = MinMaxTrackingIterator::new(concat_iter);
+
+        // Simulate a filtered stream: seek past the first few keys, then scan a bounded range.
+        let start = TEST_KEYS_COUNT - 2;
+        let end = TEST_KEYS_COUNT + 2;
+        iter.seek(iterator_test_key_of(start).to_ref())
+            .await
+            .unwrap();
+        let mut last = start;
+        while iter.is_valid() && last < end {
+            last += 1;
+            iter.next().await.unwrap();
+        }
+
+        assert_eq!(
+            iter.min_key().unwrap(),
+            iterator_test_key_of(start).to_ref()
+        );
+        assert_eq!(
+            iter.max_key().unwrap(),
+            iterator_test_key_of(end).to_ref()
+        );
+    }
+}
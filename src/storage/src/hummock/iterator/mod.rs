@@ -16,7 +16,12 @@ use std::future::Future;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
-use super::{HummockResult, HummockValue};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt};
+
+use super::{HummockError, HummockResult, HummockValue};
 
 mod forward_concat;
 pub use forward_concat::*;
@@ -24,6 +29,18 @@ mod backward_concat;
 mod concat_inner;
 pub use backward_concat::*;
 pub use concat_inner::ConcatIteratorInner;
+mod dyn_direction_concat;
+pub use dyn_direction_concat::DynDirectionConcatIterator;
+mod chain;
+pub use chain::Chain;
+mod skip;
+pub use skip::Skip;
+mod prefix_seek;
+pub use prefix_seek::PrefixSeek;
+mod min_max_tracking;
+pub use min_max_tracking::MinMaxTrackingIterator;
+mod multi_sst_iterator;
+pub use multi_sst_iterator::{MultiSstIterator, MultiSstIteratorBuilder};
 mod backward_merge;
 pub use backward_merge::*;
 mod backward_user;
@@ -33,8 +50,11 @@ pub use forward_merge::*;
 pub mod forward_user;
 mod merge_inner;
 pub use forward_user::*;
-pub use merge_inner::{OrderedMergeIteratorInner, UnorderedMergeIteratorInner};
+pub use merge_inner::{
+    MergeIterator, OrderedMergeIteratorInner, OrderedMergeTieBreak, UnorderedMergeIteratorInner,
+};
 use risingwave_hummock_sdk::key::FullKey;
+use risingwave_hummock_sdk::HummockEpoch;
 
 use crate::hummock::iterator::HummockIteratorUnion::{First, Fourth, Second, Third};
 
@@ -42,6 +62,8 @@ mod concat_delete_range_iterator;
 mod delete_range_iterator;
 #[cfg(any(test, feature = "test"))]
 pub mod test_utils;
+#[cfg(any(test, feature = "test"))]
+pub mod tracing_iterator;
 
 pub use delete_range_iterator::{
     DeleteRangeIterator, ForwardMergeRangeIterator, RangeIteratorTyped,
@@ -102,6 +124,37 @@ pub trait HummockIterator: Send + 'static {
     // TODO: Add lifetime
     fn value(&self) -> HummockValue<&[u8]>;
 
+    /// Retrieves the encoded length of the current value, i.e. what [`HummockValue::encoded_len`]
+    /// would report for [`Self::value`]. Implementors backed by a single contiguous buffer (e.g.
+    /// a block cursor) should override this to read the length directly, without paying the cost
+    /// of decoding the value.
+    ///
+    /// Note:
+    /// - Before calling this function, makes sure the iterator `is_valid`.
+    /// - This function should be straightforward and return immediately.
+    ///
+    /// # Panics
+    /// This function will panic if the iterator is invalid.
+    fn value_len(&self) -> usize {
+        self.value().encoded_len()
+    }
+
+    /// Like [`Self::value`], but returns an owned [`HummockValue<Bytes>`] instead of one
+    /// borrowed from the iterator, for callers that need to buffer values across `next` calls
+    /// (e.g. a batch of rows collected before being handed off to the caller) and would
+    /// otherwise have to copy `value()` manually. The default implementation copies; an
+    /// implementor whose underlying storage is already `Bytes`-backed should override this to
+    /// slice out of it with a cheap refcount bump instead.
+    ///
+    /// Note:
+    /// - Before calling this function, makes sure the iterator `is_valid`.
+    ///
+    /// # Panics
+    /// This function will panic if the iterator is invalid.
+    fn value_owned(&self) -> HummockValue<Bytes> {
+        self.value().to_bytes()
+    }
+
     /// Indicates whether the iterator can be used.
     ///
     /// Note:
@@ -128,6 +181,165 @@ pub trait HummockIterator: Send + 'static {
 
     /// take local statistic info from iterator to report metrics.
     fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic);
+
+    /// Skips all remaining versions of the current user key, landing on the smallest position
+    /// strictly after all of them, i.e. the first position of the next distinct user key in
+    /// forward iteration order (or the previous distinct user key for backward iterators).
+    ///
+    /// The default implementation reads the current key and seeks to it with epoch `0` (forward),
+    /// which sorts after every real version of that user key, or with `HummockEpoch::MAX`
+    /// (backward), which sorts before all of them. Implementations that can skip versions more
+    /// cheaply than a full `seek` may override this.
+    ///
+    /// Note:
+    /// - Do not decide whether the position is valid or not by checking the returned error of
+    ///   this function. This function WON'T return an `Err` if invalid. You should check
+    ///   `is_valid` before starting iteration.
+    ///
+    /// # Panics
+    /// This function will panic if the iterator is invalid.
+    fn seek_next_user_key(&mut self) -> BoxFuture<'_, HummockResult<()>> {
+        async move {
+            let key = self.key();
+            let epoch = match Self::Direction::direction() {
+                DirectionEnum::Forward => 0,
+                DirectionEnum::Backward => HummockEpoch::MAX,
+            };
+            let seek_key = FullKey {
+                user_key: key.user_key.to_vec(),
+                epoch,
+            };
+            self.seek(seek_key.to_ref()).await
+        }
+        .boxed()
+    }
+
+    /// Returns the epoch of the current key, i.e. the inverse of what `key_with_epoch` encodes.
+    /// Centralizes what would otherwise be ad-hoc access to [`FullKey::epoch`] at call sites.
+    ///
+    /// # Panics
+    /// This function will panic if the iterator is invalid.
+    fn current_epoch(&self) -> HummockEpoch {
+        self.key().epoch
+    }
+
+    /// Like [`Self::seek`], but takes an owned key instead of a borrowed one, for callers that
+    /// compute the seek key on the fly and would otherwise have to thread its lifetime through
+    /// their own code just to satisfy `seek`'s signature. Behaves identically to `seek`.
+    ///
+    /// Note:
+    /// - Do not decide whether the position is valid or not by checking the returned error of
+    ///   this function. This function WON'T return an `Err` if invalid. You should check
+    ///   `is_valid` before starting iteration.
+    fn seek_owned(&mut self, key: FullKey<Vec<u8>>) -> BoxFuture<'_, HummockResult<()>> {
+        async move { self.seek(key.to_ref()).await }.boxed()
+    }
+
+    /// Advances the iterator and returns the new key in one call, so hot scans don't need a
+    /// separate [`Self::key`] dispatch after every [`Self::next`]. Returns `None` once the
+    /// advance leaves the iterator invalid.
+    ///
+    /// Note: unlike a `poll`-based fast path, this crate's iterators are driven by `async fn`s
+    /// built on GATs rather than a manual `Poll`, so there is no synchronous fast case to skip
+    /// here; this is purely a convenience wrapper around `next`/`is_valid`/`key`.
+    ///
+    /// # Panics
+    /// This function will panic if the iterator is invalid before calling.
+    fn next_key(&mut self) -> BoxFuture<'_, HummockResult<Option<FullKey<&[u8]>>>> {
+        async move {
+            self.next().await?;
+            Ok(if self.is_valid() { Some(self.key()) } else { None })
+        }
+        .boxed()
+    }
+
+    /// Like [`Self::key`], but returns `None` instead of panicking when the iterator is
+    /// invalid, so callers that would otherwise need a defensive `is_valid` check (e.g.
+    /// dashboard/diagnostics code and test harnesses) can use `?`/`if let` instead.
+    fn try_key(&self) -> Option<FullKey<&[u8]>> {
+        self.is_valid().then(|| self.key())
+    }
+
+    /// Like [`Self::value`], but returns `None` instead of panicking when the iterator is
+    /// invalid. See [`Self::try_key`].
+    fn try_value(&self) -> Option<HummockValue<&[u8]>> {
+        self.is_valid().then(|| self.value())
+    }
+
+    /// Like [`Self::next`], but returns `Err(HummockError::iterator_invalid())` instead of
+    /// panicking when called on an already-invalid iterator. Built on [`Self::try_key`], so hosts
+    /// that embed this trait and want misuse turned into a recoverable error rather than a panic
+    /// can use this instead of checking `is_valid` themselves before every `next`.
+    fn checked_next(&mut self) -> BoxFuture<'_, HummockResult<()>>
+    where
+        Self: Sized,
+    {
+        async move {
+            if self.try_key().is_none() {
+                return Err(HummockError::iterator_invalid());
+            }
+            self.next().await
+        }
+        .boxed()
+    }
+
+    /// Like [`Self::seek`], but positions strictly after `key` instead of at the first position
+    /// `>= key`. Useful for resuming a scan right after a known last-seen key without
+    /// re-emitting it. The default implementation seeks to `key` and, if the landed position's
+    /// key is exactly `key`, advances once via [`Self::next`]; a position landing on any other
+    /// key (including an absent `key`) is left untouched, matching `seek`'s behaviour.
+    ///
+    /// Note:
+    /// - Do not decide whether the position is valid or not by checking the returned error of
+    ///   this function. This function WON'T return an `Err` if invalid. You should check
+    ///   `is_valid` before starting iteration.
+    fn seek_exclusive<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> BoxFuture<'a, HummockResult<()>> {
+        async move {
+            self.seek(key).await?;
+            if self.is_valid() && self.key() == key {
+                self.next().await?;
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    /// Consumes this iterator and turns it into a [`Stream`], so callers can use `futures`
+    /// combinators (`filter`, `map`, `take_while`, ...) instead of a manual `rewind`/`next` loop.
+    /// The returned stream first rewinds, then yields owned `(key, value)` pairs until the
+    /// iterator becomes invalid. It stops, without yielding a further item, right after the first
+    /// `Err` (from either the initial `rewind` or a later `next`).
+    fn into_stream(self) -> BoxStream<'static, HummockResult<(Vec<u8>, HummockValue<Bytes>)>>
+    where
+        Self: Sized,
+    {
+        enum State<I> {
+            Init(I),
+            Started(I),
+        }
+
+        stream::unfold(Some(State::Init(self)), |state| async move {
+            let mut iter = match state? {
+                State::Init(mut iter) => {
+                    if let Err(e) = iter.rewind().await {
+                        return Some((Err(e), None));
+                    }
+                    iter
+                }
+                State::Started(iter) => iter,
+            };
+            if !iter.is_valid() {
+                return None;
+            }
+            let key = iter.key().encode();
+            let value = iter.value_owned();
+            if let Err(e) = iter.next().await {
+                return Some((Err(e), None));
+            }
+            Some((Ok((key, value)), Some(State::Started(iter))))
+        })
+        .boxed()
+    }
 }
 
 /// This is a placeholder trait used in `HummockIteratorUnion`
@@ -339,3 +551,252 @@ impl HummockIteratorDirection for Backward {
         DirectionEnum::Backward
     }
 }
+
+/// Renders an encoded full key's raw bytes as `<user_key_hex>@<epoch>`, for panic and error
+/// messages at sites (e.g. seek assertions, ordering checks) where only the raw bytes of a key
+/// are at hand and a decoded `FullKey`'s `Debug` impl isn't convenient or available.
+pub fn debug_key(key: &[u8]) -> String {
+    let (user_key, epoch) = risingwave_hummock_sdk::key::split_key_epoch(key);
+    let epoch = HummockEpoch::from_be_bytes(epoch.try_into().unwrap());
+    format!("{}@{}", hex::encode(user_key), epoch)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::TryStreamExt;
+    use risingwave_common::cache::CachePriority;
+    use risingwave_hummock_sdk::key::key_with_epoch;
+
+    use super::*;
+    use crate::hummock::sstable::SstableIteratorReadOptions;
+    use crate::hummock::test_utils::{
+        create_small_table_cache, default_builder_opt_for_test, gen_default_test_sstable,
+        test_key_of, TEST_KEYS_COUNT,
+    };
+    use crate::hummock::SstableIterator;
+
+    #[test]
+    fn test_debug_key() {
+        let key = key_with_epoch(b"some_key".to_vec(), 233);
+        assert_eq!(debug_key(&key), format!("{}@233", hex::encode(b"some_key")));
+    }
+
+    #[tokio::test]
+    async fn test_next_key_over_cached_sst() {
+        let sstable_store = crate::hummock::iterator::test_utils::mock_sstable_store();
+        let sstable =
+            gen_default_test_sstable(default_builder_opt_for_test(), 0, sstable_store.clone())
+                .await;
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        sstable_iter.rewind().await.unwrap();
+
+        let mut cnt = 0;
+        while sstable_iter.is_valid() {
+            assert_eq!(sstable_iter.key(), test_key_of(cnt).to_ref());
+            cnt += 1;
+            match sstable_iter.next_key().await.unwrap() {
+                Some(key) => assert_eq!(key, test_key_of(cnt).to_ref()),
+                None => assert_eq!(cnt, TEST_KEYS_COUNT),
+            }
+        }
+        assert_eq!(cnt, TEST_KEYS_COUNT);
+    }
+
+    #[tokio::test]
+    async fn test_try_key_and_try_value() {
+        let sstable_store = crate::hummock::iterator::test_utils::mock_sstable_store();
+        let sstable =
+            gen_default_test_sstable(default_builder_opt_for_test(), 0, sstable_store.clone())
+                .await;
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+
+        // Not yet rewound: the iterator is invalid.
+        assert_eq!(sstable_iter.try_key(), None);
+        assert_eq!(sstable_iter.try_value(), None);
+
+        sstable_iter.rewind().await.unwrap();
+        assert_eq!(sstable_iter.try_key(), Some(test_key_of(0).to_ref()));
+        assert_eq!(sstable_iter.try_value(), Some(sstable_iter.value()));
+
+        // Exhaust the iterator.
+        while sstable_iter.is_valid() {
+            sstable_iter.next().await.unwrap();
+        }
+        assert_eq!(sstable_iter.try_key(), None);
+        assert_eq!(sstable_iter.try_value(), None);
+    }
+
+    #[tokio::test]
+    async fn test_seek_exclusive() {
+        let sstable_store = crate::hummock::iterator::test_utils::mock_sstable_store();
+        let sstable =
+            gen_default_test_sstable(default_builder_opt_for_test(), 0, sstable_store.clone())
+                .await;
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+
+        // Seeking exclusively to an existing key lands on the next key, not the key itself.
+        sstable_iter
+            .seek_exclusive(test_key_of(0).to_ref())
+            .await
+            .unwrap();
+        assert_eq!(sstable_iter.try_key(), Some(test_key_of(1).to_ref()));
+
+        // Seeking exclusively to an absent key behaves like a plain `seek`: since this
+        // iterator's keys all share a single epoch, `test_key_of(2)` with its epoch decremented
+        // never exists, so the landed key never equals the seek key and `seek_exclusive` leaves
+        // the position untouched relative to a normal `seek`.
+        let mut absent_key = test_key_of(2);
+        absent_key.epoch -= 1;
+        sstable_iter
+            .seek_exclusive(absent_key.to_ref())
+            .await
+            .unwrap();
+        let landed_via_exclusive = sstable_iter.try_key().map(|k| k.to_vec());
+
+        sstable_iter.seek(absent_key.to_ref()).await.unwrap();
+        let landed_via_plain_seek = sstable_iter.try_key().map(|k| k.to_vec());
+
+        assert_eq!(landed_via_exclusive, landed_via_plain_seek);
+    }
+
+    #[tokio::test]
+    async fn test_checked_next_on_exhausted_iterator() {
+        let sstable_store = crate::hummock::iterator::test_utils::mock_sstable_store();
+        let sstable =
+            gen_default_test_sstable(default_builder_opt_for_test(), 0, sstable_store.clone())
+                .await;
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        sstable_iter.rewind().await.unwrap();
+
+        // While valid, `checked_next` behaves just like `next`.
+        let mut cnt = 1;
+        while sstable_iter.try_key().is_some() {
+            sstable_iter.checked_next().await.unwrap();
+            cnt += 1;
+        }
+        assert_eq!(cnt, TEST_KEYS_COUNT + 1);
+
+        // Once exhausted, `checked_next` returns a typed error instead of panicking.
+        let err = sstable_iter.checked_next().await.unwrap_err();
+        assert!(err.is_iterator_invalid());
+    }
+
+    #[tokio::test]
+    async fn test_into_stream_collects_all_entries() {
+        let sstable_store = crate::hummock::iterator::test_utils::mock_sstable_store();
+        let sstable =
+            gen_default_test_sstable(default_builder_opt_for_test(), 0, sstable_store.clone())
+                .await;
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+
+        let items: Vec<_> = sstable_iter
+            .into_stream()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(items.len(), TEST_KEYS_COUNT);
+        for (i, (key, _value)) in items.into_iter().enumerate() {
+            assert_eq!(key, test_key_of(i).encode());
+        }
+    }
+
+    /// An iterator over a fixed list of keys whose `next` fails once it reaches `fail_at`, for
+    /// asserting that [`HummockIterator::into_stream`] stops right after the first error.
+    struct FailAtStepIterator {
+        keys: Vec<FullKey<Vec<u8>>>,
+        pos: usize,
+        fail_at: usize,
+    }
+
+    impl HummockIterator for FailAtStepIterator {
+        type Direction = Forward;
+
+        type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+        type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+        type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+        fn next(&mut self) -> Self::NextFuture<'_> {
+            async move {
+                if self.pos == self.fail_at {
+                    return Err(crate::hummock::HummockError::other("injected error"));
+                }
+                self.pos += 1;
+                Ok(())
+            }
+        }
+
+        fn key(&self) -> FullKey<&[u8]> {
+            self.keys[self.pos].to_ref()
+        }
+
+        fn value(&self) -> HummockValue<&[u8]> {
+            HummockValue::put(b"v")
+        }
+
+        fn is_valid(&self) -> bool {
+            self.pos < self.keys.len()
+        }
+
+        fn rewind(&mut self) -> Self::RewindFuture<'_> {
+            async move {
+                self.pos = 0;
+                Ok(())
+            }
+        }
+
+        fn seek<'a>(&'a mut self, _key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+            async move { unreachable!() }
+        }
+
+        fn collect_local_statistic(&self, _stats: &mut StoreLocalStatistic) {}
+    }
+
+    #[tokio::test]
+    async fn test_into_stream_stops_after_first_error() {
+        let keys: Vec<_> = (0..5).map(test_key_of).collect();
+        let iter = FailAtStepIterator {
+            keys,
+            pos: 0,
+            fail_at: 3,
+        };
+
+        let results: Vec<_> = iter.into_stream().collect().await;
+        assert_eq!(results.len(), 4);
+        for item in &results[..3] {
+            assert!(item.is_ok());
+        }
+        assert!(results[3].is_err());
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ConcatIterator, Forward, HummockIteratorUnion, UnorderedMergeIteratorInner};
+use crate::hummock::SstableIterator;
+
+/// An unordered merge over a mix of non-overlapping [`ConcatIterator`]s and single-SST
+/// [`SstableIterator`]s, e.g. for merging several compaction sub-levels (each a concat-able run)
+/// together with a handful of standalone overlapping SSTs.
+pub type MultiSstIterator =
+    UnorderedMergeIteratorInner<HummockIteratorUnion<Forward, ConcatIterator, SstableIterator>>;
+
+/// Builds a [`MultiSstIterator`] without requiring call sites to manually wrap each input in the
+/// right [`HummockIteratorUnion`] variant.
+#[derive(Default)]
+pub struct MultiSstIteratorBuilder {
+    iters: Vec<HummockIteratorUnion<Forward, ConcatIterator, SstableIterator>>,
+}
+
+impl MultiSstIteratorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_concat(&mut self, iter: ConcatIterator) -> &mut Self {
+        self.iters.push(HummockIteratorUnion::First(iter));
+        self
+    }
+
+    pub fn add_sstable(&mut self, iter: SstableIterator) -> &mut Self {
+        self.iters.push(HummockIteratorUnion::Second(iter));
+        self
+    }
+
+    pub fn build(self) -> MultiSstIterator {
+        MultiSstIterator::new(self.iters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::{
+        default_builder_opt_for_test, gen_iterator_test_sstable_base, iterator_test_key_of,
+        mock_sstable_store, TEST_KEYS_COUNT,
+    };
+    use crate::hummock::iterator::HummockIterator;
+    use crate::hummock::sstable::SstableIteratorReadOptions;
+
+    #[tokio::test]
+    async fn test_multi_sst_iterator_builder() {
+        let sstable_store = mock_sstable_store();
+        let concat_table = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let single_table = gen_iterator_test_sstable_base(
+            1,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+
+        let read_options = Arc::new(SstableIteratorReadOptions::default());
+        let concat_iter = ConcatIterator::new(
+            vec![concat_table.get_sstable_info()],
+            sstable_store.clone(),
+            read_options.clone(),
+        );
+        let sstable_iter = SstableIterator::new(
+            sstable_store
+                .sstable(&single_table.get_sstable_info(), &mut Default::default())
+                .await
+                .unwrap(),
+            sstable_store,
+            read_options,
+        );
+
+        let mut builder = MultiSstIteratorBuilder::new();
+        builder.add_concat(concat_iter);
+        builder.add_sstable(sstable_iter);
+        let mut iter = builder.build();
+
+        iter.rewind().await.unwrap();
+        for i in 0..TEST_KEYS_COUNT * 2 {
+            assert!(iter.is_valid());
+            assert_eq!(iter.key(), iterator_test_key_of(i).to_ref());
+            iter.next().await.unwrap();
+        }
+        assert!(!iter.is_valid());
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+
+use bytes::Bytes;
+use risingwave_common::catalog::TableId;
+use risingwave_hummock_sdk::key::{end_bound_of_prefix, FullKey, TableKey, UserKey};
+use risingwave_hummock_sdk::HummockEpoch;
+
+use super::HummockIterator;
+use crate::hummock::value::HummockValue;
+use crate::hummock::HummockResult;
+use crate::monitor::StoreLocalStatistic;
+
+/// Transparently wraps a `HummockIterator`, scoping a scan to a single table-key `prefix`.
+/// `rewind` seeks straight to the start of the prefix instead of replaying from the very
+/// beginning of the inner iterator, and `is_valid` turns false as soon as the current key walks
+/// past the prefix's exclusive upper bound, so callers get a self-terminating prefix scan without
+/// hand-building the bounds themselves. `seek` is passed straight through, as the caller has
+/// already chosen an exact starting point.
+///
+/// When `prefix` is all `0xff` bytes, it has no successor, so the upper bound is left unbounded
+/// and the scan simply runs to the end of the inner iterator.
+pub struct PrefixSeek<I: HummockIterator> {
+    inner: I,
+    table_id: TableId,
+    prefix: Bytes,
+    out_of_range: bool,
+}
+
+impl<I: HummockIterator> PrefixSeek<I> {
+    pub fn new(inner: I, table_id: TableId, prefix: Bytes) -> Self {
+        Self {
+            inner,
+            table_id,
+            prefix,
+            out_of_range: false,
+        }
+    }
+
+    fn check_out_of_range(&mut self) {
+        self.out_of_range = if !self.inner.is_valid() {
+            true
+        } else {
+            match end_bound_of_prefix(&self.prefix) {
+                Excluded(upper) => {
+                    self.inner.key().user_key >= UserKey::new(self.table_id, TableKey(&upper[..]))
+                }
+                Unbounded => false,
+                Included(_) => unreachable!("`end_bound_of_prefix` never returns `Included`"),
+            }
+        };
+    }
+}
+
+impl<I: HummockIterator> HummockIterator for PrefixSeek<I> {
+    type Direction = I::Direction;
+
+    type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+    fn next(&mut self) -> Self::NextFuture<'_> {
+        async move {
+            self.inner.next().await?;
+            self.check_out_of_range();
+            Ok(())
+        }
+    }
+
+    fn key(&self) -> FullKey<&[u8]> {
+        self.inner.key()
+    }
+
+    fn value(&self) -> HummockValue<&[u8]> {
+        self.inner.value()
+    }
+
+    fn is_valid(&self) -> bool {
+        !self.out_of_range && self.inner.is_valid()
+    }
+
+    fn rewind(&mut self) -> Self::RewindFuture<'_> {
+        async move {
+            // A larger epoch sorts first for a given user key (see `FullKey::cmp`), so seeking
+            // with `HummockEpoch::MAX` lands on the very first version of the prefix's first key.
+            let prefix = self.prefix.clone();
+            let lower_bound =
+                FullKey::new(self.table_id, TableKey(prefix.as_ref()), HummockEpoch::MAX);
+            self.inner.seek(lower_bound).await?;
+            self.check_out_of_range();
+            Ok(())
+        }
+    }
+
+    fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+        async move {
+            self.inner.seek(key).await?;
+            self.check_out_of_range();
+            Ok(())
+        }
+    }
+
+    fn collect_local_statistic(&self, stats: &mut StoreLocalStatistic) {
+        self.inner.collect_local_statistic(stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::{default_builder_opt_for_test, mock_sstable_store};
+    use crate::hummock::iterator::ConcatIterator;
+    use crate::hummock::sstable::SstableIteratorReadOptions;
+    use crate::hummock::test_utils::gen_test_sstable;
+    use crate::hummock::HummockValue;
+
+    fn table_key_of(table_key: &str) -> FullKey<Vec<u8>> {
+        FullKey::for_test(TableId::default(), table_key.as_bytes().to_vec(), 233)
+    }
+
+    async fn build_prefix_seek(prefix: &[u8]) -> PrefixSeek<ConcatIterator> {
+        let sstable_store = mock_sstable_store();
+        let kvs = ["aaa0", "aaa1", "bbb0", "bbb1", "bbb2", "ccc0"];
+        let table = gen_test_sstable(
+            default_builder_opt_for_test(),
+            0,
+            kvs.into_iter()
+                .map(|k| (table_key_of(k), HummockValue::put(b"v".to_vec()))),
+            sstable_store.clone(),
+        )
+        .await;
+        let inner = ConcatIterator::new(
+            vec![table.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        PrefixSeek::new(inner, TableId::default(), Bytes::copy_from_slice(prefix))
+    }
+
+    async fn collect_table_keys(iter: &mut PrefixSeek<ConcatIterator>) -> Vec<Vec<u8>> {
+        let mut keys = vec![];
+        iter.rewind().await.unwrap();
+        while iter.is_valid() {
+            keys.push(iter.key().user_key.table_key.0.to_vec());
+            iter.next().await.unwrap();
+        }
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_prefix_seek_yields_only_matching_keys() {
+        let mut iter = build_prefix_seek(b"bbb").await;
+        let keys = collect_table_keys(&mut iter).await;
+        assert_eq!(
+            keys,
+            vec![b"bbb0".to_vec(), b"bbb1".to_vec(), b"bbb2".to_vec()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prefix_seek_no_match() {
+        let mut iter = build_prefix_seek(b"zzz").await;
+        let keys = collect_table_keys(&mut iter).await;
+        assert!(keys.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prefix_seek_all_0xff_prefix_scans_to_end() {
+        // No successor prefix exists, so the upper bound is unbounded; there's nothing under
+        // this prefix in the test SST, so the scan still yields no keys, but it must not panic
+        // on the `unreachable!()` match arm while computing the (absent) upper bound.
+        let mut iter = build_prefix_seek(&[0xff, 0xff, 0xff]).await;
+        let keys = collect_table_keys(&mut iter).await;
+        assert!(keys.is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+
+use risingwave_hummock_sdk::key::FullKey;
+
+use super::HummockIterator;
+use crate::hummock::HummockResult;
+use crate::hummock::value::HummockValue;
+use crate::monitor::StoreLocalStatistic;
+
+/// Transparently wraps a `HummockIterator`, discarding the first `count` valid keys after every
+/// `rewind`, for offset-style pagination over a scan. The skip is re-applied on each `rewind`; a
+/// `seek` positions the inner iterator directly and is not affected by the skip count, since the
+/// caller has already chosen an exact starting point.
+pub struct Skip<I: HummockIterator> {
+    inner: I,
+    /// Number of valid keys to discard after each `rewind`.
+    count: usize,
+}
+
+impl<I: HummockIterator> Skip<I> {
+    pub fn new(inner: I, count: usize) -> Self {
+        Self { inner, count }
+    }
+
+    async fn advance_past_skip(&mut self) -> HummockResult<()> {
+        for _ in 0..self.count {
+            if !self.inner.is_valid() {
+                break;
+            }
+            self.inner.next().await?;
+        }
+        Ok(())
+    }
+}
+
+impl<I: HummockIterator> HummockIterator for Skip<I> {
+    type Direction = I::Direction;
+
+    type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+    fn next(&mut self) -> Self::NextFuture<'_> {
+        self.inner.next()
+    }
+
+    fn key(&self) -> FullKey<&[u8]> {
+        self.inner.key()
+    }
+
+    fn value(&self) -> HummockValue<&[u8]> {
+        self.inner.value()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.inner.is_valid()
+    }
+
+    fn rewind(&mut self) -> Self::RewindFuture<'_> {
+        async move {
+            self.inner.rewind().await?;
+            self.advance_past_skip().await
+        }
+    }
+
+    fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+        self.inner.seek(key)
+    }
+
+    fn collect_local_statistic(&self, stats: &mut StoreLocalStatistic) {
+        self.inner.collect_local_statistic(stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::{
+        default_builder_opt_for_test, gen_iterator_test_sstable_base, iterator_test_key_of,
+        mock_sstable_store, TEST_KEYS_COUNT,
+    };
+    use crate::hummock::iterator::ConcatIterator;
+    use crate::hummock::sstable::SstableIteratorReadOptions;
+
+    async fn build_skip(count: usize) -> Skip<ConcatIterator> {
+        let sstable_store = mock_sstable_store();
+        let table = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let inner = ConcatIterator::new(
+            vec![table.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        Skip::new(inner, count)
+    }
+
+    #[tokio::test]
+    async fn test_skip_exposes_keys_after_offset() {
+        let mut iter = build_skip(5).await;
+        iter.rewind().await.unwrap();
+
+        assert!(iter.is_valid());
+        assert_eq!(iter.key(), iterator_test_key_of(5).to_ref());
+
+        for i in 6..TEST_KEYS_COUNT {
+            iter.next().await.unwrap();
+            assert!(iter.is_valid());
+            assert_eq!(iter.key(), iterator_test_key_of(i).to_ref());
+        }
+        iter.next().await.unwrap();
+        assert!(!iter.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_skip_past_end_is_invalid() {
+        let mut iter = build_skip(TEST_KEYS_COUNT + 10).await;
+        iter.rewind().await.unwrap();
+        assert!(!iter.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_skip_reapplies_on_rewind() {
+        let mut iter = build_skip(5).await;
+        iter.rewind().await.unwrap();
+        assert_eq!(iter.key(), iterator_test_key_of(5).to_ref());
+
+        iter.next().await.unwrap();
+        assert_eq!(iter.key(), iterator_test_key_of(6).to_ref());
+
+        iter.rewind().await.unwrap();
+        assert_eq!(iter.key(), iterator_test_key_of(5).to_ref());
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use risingwave_hummock_sdk::key::FullKey;
+
+use super::HummockIterator;
+use crate::hummock::{HummockResult, HummockValue};
+use crate::monitor::StoreLocalStatistic;
+
+/// Which operation a call recorded by a [`TracingIterator`] invoked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TracedHummockIteratorOperation {
+    Rewind,
+    Seek(Vec<u8>),
+    Next,
+}
+
+/// One call recorded by a [`TracingIterator`]: which operation was invoked, and the key the
+/// iterator was left pointing at afterwards (`None` if it became invalid).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracedHummockIteratorEvent {
+    pub operation: TracedHummockIteratorOperation,
+    pub resulting_key: Option<Vec<u8>>,
+}
+
+/// Transparently wraps a `HummockIterator`, recording every `rewind`/`seek`/`next` call and the
+/// key it leaves the iterator pointing at into a shared trace. Meant for deterministic
+/// regression tests of merge/concat logic, where a test needs to assert the exact sequence of
+/// positions an algorithm visits rather than just its final output.
+pub struct TracingIterator<I: HummockIterator> {
+    inner: I,
+    trace: Arc<Mutex<Vec<TracedHummockIteratorEvent>>>,
+}
+
+impl<I: HummockIterator> TracingIterator<I> {
+    pub fn new(inner: I, trace: Arc<Mutex<Vec<TracedHummockIteratorEvent>>>) -> Self {
+        Self { inner, trace }
+    }
+
+    fn record(&self, operation: TracedHummockIteratorOperation) {
+        let resulting_key = self.inner.is_valid().then(|| self.inner.key().encode());
+        self.trace.lock().unwrap().push(TracedHummockIteratorEvent {
+            operation,
+            resulting_key,
+        });
+    }
+}
+
+impl<I: HummockIterator> HummockIterator for TracingIterator<I> {
+    type Direction = I::Direction;
+
+    type NextFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type RewindFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+    type SeekFuture<'a> = impl Future<Output = HummockResult<()>> + 'a;
+
+    fn next(&mut self) -> Self::NextFuture<'_> {
+        async move {
+            self.inner.next().await?;
+            self.record(TracedHummockIteratorOperation::Next);
+            Ok(())
+        }
+    }
+
+    fn key(&self) -> FullKey<&[u8]> {
+        self.inner.key()
+    }
+
+    fn value(&self) -> HummockValue<&[u8]> {
+        self.inner.value()
+    }
+
+    fn value_len(&self) -> usize {
+        self.inner.value_len()
+    }
+
+    fn value_owned(&self) -> HummockValue<Bytes> {
+        self.inner.value_owned()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.inner.is_valid()
+    }
+
+    fn rewind(&mut self) -> Self::RewindFuture<'_> {
+        async move {
+            self.inner.rewind().await?;
+            self.record(TracedHummockIteratorOperation::Rewind);
+            Ok(())
+        }
+    }
+
+    fn seek<'a>(&'a mut self, key: FullKey<&'a [u8]>) -> Self::SeekFuture<'a> {
+        async move {
+            let traced_key = key.encode();
+            self.inner.seek(key).await?;
+            self.record(TracedHummockIteratorOperation::Seek(traced_key));
+            Ok(())
+        }
+    }
+
+    fn collect_local_statistic(&self, stats: &mut StoreLocalStatistic) {
+        self.inner.collect_local_statistic(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::{
+        default_builder_opt_for_test, gen_iterator_test_sstable_base, iterator_test_key_of,
+        mock_sstable_store, TEST_KEYS_COUNT,
+    };
+    use crate::hummock::iterator::ConcatIterator;
+    use crate::hummock::sstable::SstableIteratorReadOptions;
+
+    #[tokio::test]
+    async fn test_tracing_iterator_records_seek_then_scan() {
+        let sstable_store = mock_sstable_store();
+        let table0 = gen_iterator_test_sstable_base(
+            0,
+            default_builder_opt_for_test(),
+            |x| x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let table1 = gen_iterator_test_sstable_base(
+            1,
+            default_builder_opt_for_test(),
+            |x| TEST_KEYS_COUNT + x,
+            sstable_store.clone(),
+            TEST_KEYS_COUNT,
+        )
+        .await;
+        let concat_iter = ConcatIterator::new(
+            vec![table0.get_sstable_info(), table1.get_sstable_info()],
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+
+        let trace = Arc::new(Mutex::new(Vec::new()));
+        let mut iter = TracingIterator::new(concat_iter, trace.clone());
+
+        let seek_key = iterator_test_key_of(TEST_KEYS_COUNT - 2);
+        iter.seek(seek_key.to_ref()).await.unwrap();
+        for _ in 0..3 {
+            if !iter.is_valid() {
+                break;
+            }
+            iter.next().await.unwrap();
+        }
+
+        let golden: Vec<_> = std::iter::once(TracedHummockIteratorOperation::Seek(
+            seek_key.encode(),
+        ))
+        .chain(std::iter::repeat(TracedHummockIteratorOperation::Next).take(3))
+        .collect();
+        let recorded: Vec<_> = trace
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| event.operation.clone())
+            .collect();
+        assert_eq!(recorded, golden);
+
+        // The recorded keys track the true position of the wrapped iterator at each step.
+        assert_eq!(
+            trace.lock().unwrap()[0].resulting_key,
+            Some(iterator_test_key_of(TEST_KEYS_COUNT - 2).encode())
+        );
+        assert_eq!(
+            trace.lock().unwrap()[1].resulting_key,
+            Some(iterator_test_key_of(TEST_KEYS_COUNT - 1).encode())
+        );
+        assert_eq!(
+            trace.lock().unwrap()[2].resulting_key,
+            Some(iterator_test_key_of(TEST_KEYS_COUNT).encode())
+        );
+    }
+}
@@ -149,6 +149,11 @@ pub struct Block {
 
     /// Restart points.
     restart_points: Vec<RestartPoint>,
+
+    /// Checksum of [`Self::raw_data`], taken once at decode time. Lets a later caller, e.g. one
+    /// holding a block fetched straight out of the block cache, confirm that the bytes haven't
+    /// been silently corrupted since decoding, via [`Self::verify_checksum`].
+    checksum: u64,
 }
 
 impl Block {
@@ -174,7 +179,7 @@ impl Block {
                 debug_assert_eq!(decoded.capacity(), uncompressed_capacity);
                 Bytes::from(decoded)
             }
-            CompressionAlgorithm::Zstd => {
+            CompressionAlgorithm::Zstd { .. } => {
                 let mut decoder = zstd::Decoder::new(compressed_data.reader())
                     .map_err(HummockError::decode_error)?;
                 let mut decoded = Vec::with_capacity(uncompressed_capacity);
@@ -190,6 +195,7 @@ impl Block {
     }
 
     pub fn decode_from_raw(buf: Bytes) -> Self {
+        let checksum = xxhash64_checksum(&buf);
         let table_id = (&buf[buf.len() - 4..]).get_u32_le();
         // decode restart_points_type_index
         let n_index = ((&buf[buf.len() - 8..buf.len() - 4]).get_u32_le()) as usize;
@@ -241,6 +247,7 @@ impl Block {
             data_len,
             restart_points,
             table_id: TableId::new(table_id),
+            checksum,
         }
     }
 
@@ -283,9 +290,24 @@ impl Block {
         &self.data[..self.data_len]
     }
 
+    /// Like [`Self::data`], but returns an owned [`Bytes`] sharing this block's underlying
+    /// buffer via a cheap refcount bump, for callers that need to slice out and keep a piece of
+    /// the block's data without a heap copy (e.g. [`BlockIterator::value_bytes`]).
+    pub fn data_bytes(&self) -> Bytes {
+        self.data.slice(..self.data_len)
+    }
+
     pub fn raw_data(&self) -> &[u8] {
         &self.data[..]
     }
+
+    /// Recomputes the checksum of [`Self::raw_data`] and compares it against the checksum taken
+    /// when this block was decoded, to catch corruption of an already-decoded block sitting in
+    /// the block cache (which [`Self::decode`]'s checksum check, run only once at decode time,
+    /// cannot).
+    pub fn verify_checksum(&self) -> HummockResult<()> {
+        xxhash64_verify(self.raw_data(), self.checksum)
+    }
 }
 
 /// [`KeyPrefix`] contains info for prefix compression.
@@ -366,6 +388,12 @@ impl KeyPrefix {
     }
 }
 
+/// When [`BlockBuilderOptions::adaptive_restart`] is enabled, a restart point's interval is
+/// multiplied by up to this factor while consecutive keys keep sharing at least half their
+/// length, trading a few extra bytes of diff-decoding work at read time for fewer restart points
+/// (and their associated full-key copies) on long common-prefix runs.
+const ADAPTIVE_RESTART_INTERVAL_MULTIPLIER: usize = 4;
+
 pub struct BlockBuilderOptions {
     /// Reserved bytes size when creating buffer to avoid frequent allocating.
     pub capacity: usize,
@@ -373,6 +401,11 @@ pub struct BlockBuilderOptions {
     pub compression_algorithm: CompressionAlgorithm,
     /// Restart point interval.
     pub restart_interval: usize,
+    /// Whether to widen the restart point interval (up to
+    /// [`ADAPTIVE_RESTART_INTERVAL_MULTIPLIER`]x) while consecutive keys share a long common
+    /// prefix, saving space on SSTs with long shared key prefixes at the cost of slightly slower
+    /// seeks within the widened runs.
+    pub adaptive_restart: bool,
 }
 
 impl Default for BlockBuilderOptions {
@@ -381,6 +414,7 @@ impl Default for BlockBuilderOptions {
             capacity: DEFAULT_BLOCK_SIZE,
             compression_algorithm: CompressionAlgorithm::None,
             restart_interval: DEFAULT_RESTART_INTERVAL,
+            adaptive_restart: false,
         }
     }
 }
@@ -391,12 +425,18 @@ pub struct BlockBuilder {
     buf: BytesMut,
     /// Entry interval between restart points.
     restart_count: usize,
+    /// Whether to widen the restart point interval on long common-prefix runs. See
+    /// [`BlockBuilderOptions::adaptive_restart`].
+    adaptive_restart: bool,
     /// Restart points.
     restart_points: Vec<u32>,
     /// Last key.
     last_key: Vec<u8>,
     /// Count of entries in current block.
     entry_count: usize,
+    /// Count of entries added since the last restart point, including the restart point itself.
+    /// Reset to `0` every time a new restart point is inserted.
+    entries_since_restart: usize,
     /// Compression algorithm.
     compression_algorithm: CompressionAlgorithm,
 
@@ -412,11 +452,13 @@ impl BlockBuilder {
             // add more space to avoid re-allocate space.
             buf: BytesMut::with_capacity(options.capacity + 256),
             restart_count: options.restart_interval,
+            adaptive_restart: options.adaptive_restart,
             restart_points: Vec::with_capacity(
                 options.capacity / DEFAULT_ENTRY_SIZE / options.restart_interval + 1,
             ),
             last_key: vec![],
             entry_count: 0,
+            entries_since_restart: 0,
             compression_algorithm: options.compression_algorithm,
             table_id: None,
             restart_points_type_index: Vec::default(),
@@ -473,10 +515,28 @@ impl BlockBuilder {
             true
         };
 
-        let diff_key = if self.entry_count % self.restart_count == 0 || type_mismatch {
+        let diff_key_candidate = bytes_diff_below_max_key_length(&self.last_key, &key[..]);
+        let restart_interval = if self.adaptive_restart && !key.is_empty() {
+            // Consecutive keys sharing at least half their length compress well as diffs, so
+            // widen the interval between restart points (which store the full key) to save space.
+            let shared_len = key.len() - diff_key_candidate.len();
+            if shared_len * 2 >= key.len() {
+                self.restart_count * ADAPTIVE_RESTART_INTERVAL_MULTIPLIER
+            } else {
+                self.restart_count
+            }
+        } else {
+            self.restart_count
+        };
+
+        let diff_key = if self.entry_count == 0
+            || self.entries_since_restart >= restart_interval
+            || type_mismatch
+        {
             let offset = self.buf.len() as u32;
 
             self.restart_points.push(offset);
+            self.entries_since_restart = 0;
 
             if type_mismatch {
                 self.restart_points_type_index.push(RestartPoint {
@@ -488,8 +548,9 @@ impl BlockBuilder {
 
             key.as_ref()
         } else {
-            bytes_diff_below_max_key_length(&self.last_key, &key[..])
+            diff_key_candidate
         };
+        self.entries_since_restart += 1;
 
         let prefix = KeyPrefix::new_without_len(
             key.len() - diff_key.len(),
@@ -522,6 +583,7 @@ impl BlockBuilder {
         self.restart_points_type_index.clear();
         self.last_key.clear();
         self.entry_count = 0;
+        self.entries_since_restart = 0;
     }
 
     /// Calculate block size without compression.
@@ -590,11 +652,13 @@ impl BlockBuilder {
                 result.map_err(HummockError::encode_error).unwrap();
                 self.buf = writer.into_inner();
             }
-            CompressionAlgorithm::Zstd => {
-                let mut encoder =
-                    zstd::Encoder::new(BytesMut::with_capacity(self.buf.len()).writer(), 4)
-                        .map_err(HummockError::encode_error)
-                        .unwrap();
+            CompressionAlgorithm::Zstd { level } => {
+                let mut encoder = zstd::Encoder::new(
+                    BytesMut::with_capacity(self.buf.len()).writer(),
+                    level,
+                )
+                .map_err(HummockError::encode_error)
+                .unwrap();
                 encoder
                     .write_all(&self.buf[..])
                     .map_err(HummockError::encode_error)
@@ -644,6 +708,7 @@ mod tests {
     use risingwave_hummock_sdk::key::{FullKey, MAX_KEY_LEN};
 
     use super::*;
+    use crate::hummock::sstable::utils::ZSTD_DEFAULT_COMPRESSION_LEVEL;
     use crate::hummock::{BlockHolder, BlockIterator};
 
     #[test]
@@ -684,10 +749,30 @@ mod tests {
         assert!(!bi.is_valid());
     }
 
+    #[test]
+    fn test_block_verify_checksum() {
+        let options = BlockBuilderOptions::default();
+        let mut builder = BlockBuilder::new(options);
+        builder.add_for_test(construct_full_key_struct(0, b"k1", 1), b"v01");
+        let capacity = builder.uncompressed_block_size();
+        let buf = builder.build().to_vec();
+        let mut block = Block::decode(buf.into(), capacity).unwrap();
+        block.verify_checksum().unwrap();
+
+        // Corrupting the decoded data, e.g. as if the block got corrupted while sitting in the
+        // block cache, must be caught even though the block was already decoded successfully.
+        let mut corrupted = BytesMut::from(&block.data[..]);
+        corrupted[0] ^= 1;
+        block.data = corrupted.freeze();
+        block.verify_checksum().unwrap_err();
+    }
+
     #[test]
     fn test_compressed_block_enc_dec() {
         inner_test_compressed(CompressionAlgorithm::Lz4);
-        inner_test_compressed(CompressionAlgorithm::Zstd);
+        inner_test_compressed(CompressionAlgorithm::Zstd {
+            level: ZSTD_DEFAULT_COMPRESSION_LEVEL,
+        });
     }
 
     fn inner_test_compressed(algo: CompressionAlgorithm) {
@@ -15,7 +15,7 @@
 use std::cmp::Ordering;
 use std::ops::Range;
 
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use risingwave_common::catalog::TableId;
 use risingwave_hummock_sdk::key::FullKey;
 
@@ -90,6 +90,14 @@ impl BlockIterator {
         &self.block.data()[self.value_range.clone()]
     }
 
+    /// Like [`Self::value`], but returns an owned [`Bytes`] sharing the block's underlying
+    /// buffer via a cheap refcount bump instead of a borrow, so callers can hold onto the value
+    /// past the next call to `next`/`prev`.
+    pub fn value_bytes(&self) -> Bytes {
+        assert!(self.is_valid());
+        self.block.data_bytes().slice(self.value_range.clone())
+    }
+
     pub fn is_valid(&self) -> bool {
         self.offset < self.block.len()
     }
@@ -48,6 +48,19 @@ pub struct SstableBuilderOptions {
     pub bloom_false_positive: f64,
     /// Compression algorithm.
     pub compression_algorithm: CompressionAlgorithm,
+    /// Approximate maximum number of keys per sstable, regardless of `capacity`. Useful for
+    /// workloads with tiny values, where a byte-capacity-only limit would otherwise produce
+    /// sstables with millions of keys that are slow to bloom-filter and index.
+    /// `u64::MAX` disables the limit.
+    pub max_sst_key_count: u64,
+    /// Whether to build a bloom filter for the sstable. Disabling this saves build CPU and space
+    /// for sstables where point lookups never happen (e.g. pure scan tiers); seeks simply skip
+    /// consulting the filter, exactly as they already do when `bloom_false_positive` is `0.0`.
+    pub build_bloom_filter: bool,
+    /// Whether to widen a block's restart point interval on runs of keys with a long common
+    /// prefix, saving space at the cost of slightly slower seeks within those runs. See
+    /// [`super::block::BlockBuilderOptions::adaptive_restart`].
+    pub adaptive_restart: bool,
 }
 
 impl From<&StorageOpts> for SstableBuilderOptions {
@@ -59,6 +72,9 @@ impl From<&StorageOpts> for SstableBuilderOptions {
             restart_interval: DEFAULT_RESTART_INTERVAL,
             bloom_false_positive: options.bloom_false_positive,
             compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
         }
     }
 }
@@ -71,6 +87,9 @@ impl Default for SstableBuilderOptions {
             restart_interval: DEFAULT_RESTART_INTERVAL,
             bloom_false_positive: DEFAULT_BLOOM_FALSE_POSITIVE,
             compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
         }
     }
 }
@@ -160,6 +179,7 @@ impl<W: SstableWriter, F: FilterBuilder> SstableBuilder<W, F> {
                 capacity: options.block_capacity,
                 restart_interval: options.restart_interval,
                 compression_algorithm: options.compression_algorithm,
+                adaptive_restart: options.adaptive_restart,
             }),
             filter_builder,
             block_metas: Vec::with_capacity(options.capacity / options.block_capacity + 1),
@@ -229,8 +249,29 @@ impl<W: SstableWriter, F: FilterBuilder> SstableBuilder<W, F> {
         }
 
         // TODO: refine me
+        let raw_key_len = self.raw_key.len();
+        let raw_value_len = self.raw_value.len();
         full_key.encode_into(&mut self.raw_key);
         value.encode(&mut self.raw_value);
+
+        if !self.last_full_key.is_empty()
+            && !KeyComparator::encoded_full_key_less_than(&self.last_full_key, &self.raw_key)
+        {
+            let msg = format!(
+                "SstableBuilder::add received keys out of order: previous key {:?}, new key {:?}",
+                FullKey::decode(&self.last_full_key),
+                full_key,
+            );
+            if cfg!(debug_assertions) {
+                panic!("{msg}");
+            }
+            // Roll back the just-appended bytes so the rejected key/value don't corrupt the
+            // next call's encoding.
+            self.raw_key.truncate(raw_key_len);
+            self.raw_value.truncate(raw_value_len);
+            return Err(HummockError::unsorted_sst_key(msg));
+        }
+
         if is_new_user_key {
             let table_id = full_key.user_key.table_id.table_id();
             is_new_table = self.last_table_id.is_none() || self.last_table_id.unwrap() != table_id;
@@ -363,7 +404,8 @@ impl<W: SstableWriter, F: FilterBuilder> SstableBuilder<W, F> {
         }
         self.total_key_count += self.monotonic_deletes.len() as u64;
         self.stale_key_count += self.monotonic_deletes.len() as u64;
-        let bloom_filter = if self.options.bloom_false_positive > 0.0 {
+        let bloom_filter = if self.options.build_bloom_filter && self.options.bloom_false_positive > 0.0
+        {
             self.filter_builder.finish()
         } else {
             vec![]
@@ -515,9 +557,11 @@ impl<W: SstableWriter, F: FilterBuilder> SstableBuilder<W, F> {
         self.total_key_count > 0
     }
 
-    /// Returns true if we roughly reached capacity
+    /// Returns true if we roughly reached capacity, either by size or, if configured, by key
+    /// count.
     pub fn reach_capacity(&self) -> bool {
         self.approximate_len() >= self.options.capacity
+            || self.total_key_count >= self.options.max_sst_key_count
     }
 
     fn finalize_last_table_stats(&mut self) {
@@ -533,15 +577,18 @@ impl<W: SstableWriter, F: FilterBuilder> SstableBuilder<W, F> {
 
 #[cfg(test)]
 pub(super) mod tests {
+    use risingwave_common::cache::CachePriority;
     use risingwave_common::catalog::TableId;
     use risingwave_hummock_sdk::key::UserKey;
 
     use super::*;
     use crate::assert_bytes_eq;
     use crate::hummock::iterator::test_utils::mock_sstable_store;
+    use crate::hummock::iterator::HummockIterator;
+    use crate::hummock::sstable::{SstableIterator, SstableIteratorReadOptions};
     use crate::hummock::test_utils::{
-        default_builder_opt_for_test, gen_test_sstable_impl, mock_sst_writer, test_key_of,
-        test_value_of, TEST_KEYS_COUNT,
+        create_small_table_cache, default_builder_opt_for_test, gen_test_sstable_impl,
+        mock_sst_writer, test_key_of, test_value_of, TEST_KEYS_COUNT,
     };
     use crate::hummock::{CachePolicy, Sstable, Xor16FilterBuilder, Xor8FilterBuilder};
 
@@ -553,6 +600,9 @@ pub(super) mod tests {
             restart_interval: 16,
             bloom_false_positive: 0.001,
             compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
         };
 
         let b = SstableBuilder::for_test(0, mock_sst_writer(&opt), opt);
@@ -568,6 +618,9 @@ pub(super) mod tests {
             restart_interval: 16,
             bloom_false_positive: 0.1,
             compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
         };
         let table_id = TableId::default();
         let mut b = SstableBuilder::for_test(0, mock_sst_writer(&opt), opt);
@@ -620,6 +673,29 @@ pub(super) mod tests {
         assert_eq!(meta2, meta);
     }
 
+    #[tokio::test]
+    #[should_panic(expected = "out of order")]
+    async fn test_add_rejects_out_of_order_keys() {
+        let opt = default_builder_opt_for_test();
+        let mut b = SstableBuilder::for_test(0, mock_sst_writer(&opt), opt);
+
+        b.add_for_test(
+            test_key_of(1).to_ref(),
+            HummockValue::put(&test_value_of(1)),
+            true,
+        )
+        .await
+        .unwrap();
+        // Fed out of order: smaller key after a larger one.
+        let _ = b
+            .add_for_test(
+                test_key_of(0).to_ref(),
+                HummockValue::put(&test_value_of(0)),
+                true,
+            )
+            .await;
+    }
+
     async fn test_with_bloom_filter<F: FilterBuilder>(with_blooms: bool) {
         let key_count = 1000;
 
@@ -629,6 +705,9 @@ pub(super) mod tests {
             restart_interval: 16,
             bloom_false_positive: if with_blooms { 0.01 } else { 0.0 },
             compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
         };
 
         // build remote table
@@ -659,4 +738,116 @@ pub(super) mod tests {
         test_with_bloom_filter::<Xor16FilterBuilder>(true).await;
         test_with_bloom_filter::<Xor8FilterBuilder>(true).await;
     }
+
+    #[tokio::test]
+    async fn test_build_bloom_filter_toggle() {
+        async fn build(build_bloom_filter: bool) -> (Sstable, usize) {
+            let opts = SstableBuilderOptions {
+                capacity: 0,
+                block_capacity: 4096,
+                restart_interval: 16,
+                bloom_false_positive: 0.01,
+                compression_algorithm: CompressionAlgorithm::None,
+                max_sst_key_count: u64::MAX,
+                build_bloom_filter,
+                adaptive_restart: false,
+            };
+            let sstable_store = mock_sstable_store();
+            let (table, _) = gen_test_sstable_impl::<Vec<u8>, Xor16FilterBuilder>(
+                opts,
+                0,
+                (0..TEST_KEYS_COUNT)
+                    .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+                vec![],
+                sstable_store,
+                CachePolicy::NotFill,
+            )
+            .await;
+            let file_size = table.meta.estimated_size as usize;
+            (table, file_size)
+        }
+
+        let (with_filter, with_filter_size) = build(true).await;
+        let (without_filter, without_filter_size) = build(false).await;
+        assert!(with_filter.has_bloom_filter());
+        assert!(!without_filter.has_bloom_filter());
+        assert!(with_filter_size > without_filter_size);
+
+        let cache = create_small_table_cache();
+        let with_filter_handle = cache.insert(0, 0, 1, Box::new(with_filter), CachePriority::High);
+        let without_filter_handle =
+            cache.insert(1, 1, 1, Box::new(without_filter), CachePriority::High);
+        let sstable_store = mock_sstable_store();
+
+        for i in 0..TEST_KEYS_COUNT {
+            let key = test_key_of(i);
+            for handle in [with_filter_handle.clone(), without_filter_handle.clone()] {
+                let mut iter = SstableIterator::create(
+                    handle,
+                    sstable_store.clone(),
+                    Arc::new(SstableIteratorReadOptions::default()),
+                );
+                iter.seek(key.to_ref()).await.unwrap();
+                assert!(iter.is_valid());
+                assert_eq!(iter.key(), key.to_ref());
+                assert_eq!(
+                    iter.value().into_user_value().unwrap(),
+                    test_value_of(i).as_slice()
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_restart() {
+        async fn build(adaptive_restart: bool) -> (Sstable, usize) {
+            let opts = SstableBuilderOptions {
+                capacity: 0,
+                block_capacity: 4096,
+                restart_interval: 16,
+                bloom_false_positive: 0.01,
+                compression_algorithm: CompressionAlgorithm::None,
+                max_sst_key_count: u64::MAX,
+                build_bloom_filter: false,
+                adaptive_restart,
+            };
+            let sstable_store = mock_sstable_store();
+            let (table, _) = gen_test_sstable_impl::<Vec<u8>, Xor16FilterBuilder>(
+                opts,
+                0,
+                (0..TEST_KEYS_COUNT)
+                    .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+                vec![],
+                sstable_store,
+                CachePolicy::NotFill,
+            )
+            .await;
+            let file_size = table.meta.estimated_size as usize;
+            (table, file_size)
+        }
+
+        let (adaptive, adaptive_size) = build(true).await;
+        let (non_adaptive, non_adaptive_size) = build(false).await;
+        assert!(adaptive_size < non_adaptive_size);
+
+        let cache = create_small_table_cache();
+        let adaptive_handle = cache.insert(0, 0, 1, Box::new(adaptive), CachePriority::High);
+        let sstable_store = mock_sstable_store();
+
+        for i in 0..TEST_KEYS_COUNT {
+            let key = test_key_of(i);
+            let mut iter = SstableIterator::create(
+                adaptive_handle.clone(),
+                sstable_store.clone(),
+                Arc::new(SstableIteratorReadOptions::default()),
+            );
+            iter.seek(key.to_ref()).await.unwrap();
+            assert!(iter.is_valid());
+            assert_eq!(iter.key(), key.to_ref());
+            assert_eq!(
+                iter.value().into_user_value().unwrap(),
+                test_value_of(i).as_slice()
+            );
+        }
+    }
 }
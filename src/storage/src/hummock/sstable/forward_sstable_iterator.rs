@@ -18,6 +18,7 @@ use std::future::Future;
 use std::ops::Bound::*;
 use std::sync::Arc;
 
+use bytes::Bytes;
 use risingwave_hummock_sdk::key::FullKey;
 
 use super::super::{HummockResult, HummockValue};
@@ -30,6 +31,17 @@ use crate::hummock::{
 };
 use crate::monitor::StoreLocalStatistic;
 
+/// Per-table aggregate statistics produced by [`SstableIterator::summarize`], used by the
+/// compaction planner to make split decisions finer-grained than `SstableInfo::file_size` alone.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableSummary {
+    pub min_key: Vec<u8>,
+    pub max_key: Vec<u8>,
+    pub entry_count: u64,
+    pub total_value_bytes: u64,
+    pub delete_count: u64,
+}
+
 pub trait SstableIteratorType: HummockIterator + 'static {
     fn create(
         sstable: TableHolder,
@@ -229,6 +241,76 @@ impl SstableIterator {
         &self.sst
     }
 
+    /// Drains up to `max` key-value pairs starting from the current position into `out`,
+    /// decoding directly from the currently loaded block without going through the
+    /// [`HummockIterator::next`] async/poll machinery for every entry. When the block is
+    /// exhausted before `max` is reached, the next block is fetched (the one async step of this
+    /// call) and the iterator is left positioned at its first entry, exactly as
+    /// [`HummockIterator::next`] would leave it.
+    ///
+    /// Returns the number of entries produced and whether a block boundary was crossed while
+    /// producing them.
+    ///
+    /// # Panics
+    /// This function will panic if the iterator is invalid.
+    pub async fn advance_within_block(
+        &mut self,
+        out: &mut Vec<(FullKey<Vec<u8>>, HummockValue<Vec<u8>>)>,
+        max: usize,
+    ) -> HummockResult<(usize, bool)> {
+        let mut produced = 0;
+        let mut crossed_block_boundary = false;
+        while produced < max && self.is_valid() {
+            let block_iter = self.block_iter.as_mut().expect("no block iter");
+            let key = block_iter.key().to_vec();
+            let value = match HummockValue::from_slice(block_iter.value()).expect("decode error") {
+                HummockValue::Put(v) => HummockValue::Put(v.to_vec()),
+                HummockValue::Delete => HummockValue::Delete,
+            };
+            out.push((key, value));
+            produced += 1;
+            if !block_iter.try_next() {
+                crossed_block_boundary = true;
+                self.seek_idx(self.cur_idx + 1, None).await?;
+                break;
+            }
+        }
+        self.stats.total_key_count += produced as u64;
+        Ok((produced, crossed_block_boundary))
+    }
+
+    /// Rewinds and scans the whole table once, aggregating per-table statistics for the
+    /// compaction planner. Cheaper for planning purposes than reasoning from the coarse
+    /// `file_size` alone, since it reports the actual key range and value volume.
+    pub async fn summarize(&mut self) -> HummockResult<TableSummary> {
+        self.rewind().await?;
+        let mut min_key = None;
+        let mut max_key = None;
+        let mut entry_count = 0u64;
+        let mut total_value_bytes = 0u64;
+        let mut delete_count = 0u64;
+        while self.is_valid() {
+            let key = self.key();
+            if min_key.is_none() {
+                min_key = Some(key.encode());
+            }
+            max_key = Some(key.encode());
+            entry_count += 1;
+            match self.value() {
+                HummockValue::Put(v) => total_value_bytes += v.len() as u64,
+                HummockValue::Delete => delete_count += 1,
+            }
+            self.next().await?;
+        }
+        Ok(TableSummary {
+            min_key: min_key.unwrap_or_default(),
+            max_key: max_key.unwrap_or_default(),
+            entry_count,
+            total_value_bytes,
+            delete_count,
+        })
+    }
+
     /// Seeks to a block, and then seeks to the key if `seek_key` is given.
     async fn seek_idx(
         &mut self,
@@ -254,6 +336,9 @@ impl SstableIterator {
                 .block_fetcher
                 .get_block(self.sst.value(), idx, &self.sstable_store, &mut self.stats)
                 .await?;
+            if self.options.verify_checksum {
+                block.verify_checksum()?;
+            }
             let mut block_iter = BlockIterator::new(block);
             if let Some(key) = seek_key {
                 block_iter.seek(key);
@@ -278,6 +363,13 @@ impl HummockIterator for SstableIterator {
 
     fn next(&mut self) -> Self::NextFuture<'_> {
         self.stats.total_key_count += 1;
+        if self.options.collect_histogram {
+            let value_len = self.value_len();
+            self.stats
+                .value_size_histogram
+                .get_or_insert_with(Default::default)
+                .record(value_len);
+        }
         async move {
             let block_iter = self.block_iter.as_mut().expect("no block iter");
             if block_iter.try_next() {
@@ -299,6 +391,16 @@ impl HummockIterator for SstableIterator {
         HummockValue::from_slice(raw_value).expect("decode error")
     }
 
+    fn value_len(&self) -> usize {
+        self.block_iter.as_ref().expect("no block iter").value().len()
+    }
+
+    fn value_owned(&self) -> HummockValue<Bytes> {
+        let raw_value = self.block_iter.as_ref().expect("no block iter").value_bytes();
+
+        HummockValue::from_encoded_bytes(raw_value).expect("decode error")
+    }
+
     fn is_valid(&self) -> bool {
         self.block_iter.as_ref().map_or(false, |i| i.is_valid())
     }
@@ -362,11 +464,16 @@ mod tests {
 
     use super::*;
     use crate::assert_bytes_eq;
-    use crate::hummock::iterator::test_utils::mock_sstable_store;
+    use crate::hummock::iterator::test_utils::{
+        gen_iterator_test_sstable_from_kv_pair, iterator_test_key_of_epoch,
+        iterator_test_value_of, mock_sstable_store,
+    };
+    use crate::hummock::sstable::utils::ZSTD_DEFAULT_COMPRESSION_LEVEL;
     use crate::hummock::test_utils::{
         create_small_table_cache, default_builder_opt_for_test, gen_default_test_sstable,
         gen_test_sstable, test_key_of, test_value_of, TEST_KEYS_COUNT,
     };
+    use crate::hummock::{CompressionAlgorithm, SstableBuilderOptions};
 
     async fn inner_test_forward_iterator(sstable_store: SstableStoreRef, handle: TableHolder) {
         // We should have at least 10 blocks, so that sstable iterator test could cover more code
@@ -407,6 +514,172 @@ mod tests {
         inner_test_forward_iterator(sstable_store.clone(), handle).await;
     }
 
+    #[tokio::test]
+    async fn test_table_iterator_value_owned() {
+        let sstable_store = mock_sstable_store();
+        let sstable =
+            gen_default_test_sstable(default_builder_opt_for_test(), 0, sstable_store.clone())
+                .await;
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        sstable_iter.rewind().await.unwrap();
+
+        // Collect owned values across several `next` calls, then check they're all still valid
+        // and correct, i.e. that they don't alias a buffer the iterator has since moved past.
+        let mut owned_values = Vec::new();
+        for _ in 0..TEST_KEYS_COUNT {
+            owned_values.push(sstable_iter.value_owned());
+            sstable_iter.next().await.unwrap();
+        }
+
+        for (i, value) in owned_values.into_iter().enumerate() {
+            assert_bytes_eq!(value.into_user_value().unwrap(), test_value_of(i));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_table_iterator_with_zstd_compression() {
+        // Build remote sstable compressed with zstd.
+        let opts = SstableBuilderOptions {
+            compression_algorithm: CompressionAlgorithm::Zstd {
+                level: ZSTD_DEFAULT_COMPRESSION_LEVEL,
+            },
+            ..default_builder_opt_for_test()
+        };
+        let sstable_store = mock_sstable_store();
+        let sstable = gen_default_test_sstable(opts, 0, sstable_store.clone()).await;
+
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        inner_test_forward_iterator(sstable_store.clone(), handle).await;
+    }
+
+    #[tokio::test]
+    async fn test_table_iterator_advance_within_block() {
+        let sstable_store = mock_sstable_store();
+        let sstable =
+            gen_default_test_sstable(default_builder_opt_for_test(), 0, sstable_store.clone())
+                .await;
+        assert!(sstable.meta.block_metas.len() > 10);
+
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        sstable_iter.rewind().await.unwrap();
+
+        let mut cnt = 0;
+        let mut buf = Vec::new();
+        while sstable_iter.is_valid() {
+            buf.clear();
+            let (produced, _) = sstable_iter
+                .advance_within_block(&mut buf, TEST_KEYS_COUNT)
+                .await
+                .unwrap();
+            assert_eq!(produced, buf.len());
+            for (key, value) in &buf {
+                assert_eq!(key.to_ref(), test_key_of(cnt).to_ref());
+                assert_bytes_eq!(value.as_slice().into_user_value().unwrap(), test_value_of(cnt));
+                cnt += 1;
+            }
+        }
+
+        assert_eq!(cnt, TEST_KEYS_COUNT);
+    }
+
+    #[tokio::test]
+    async fn test_table_iterator_current_epoch() {
+        let sstable_store = mock_sstable_store();
+        let sstable = gen_iterator_test_sstable_from_kv_pair(
+            0,
+            vec![(0, 42, HummockValue::put(b"v".to_vec()))],
+            sstable_store.clone(),
+        )
+        .await;
+
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        sstable_iter.rewind().await.unwrap();
+        assert_eq!(sstable_iter.current_epoch(), 42);
+    }
+
+    #[tokio::test]
+    async fn test_table_iterator_summarize() {
+        let sstable_store = mock_sstable_store();
+        let sstable = gen_iterator_test_sstable_from_kv_pair(
+            0,
+            vec![
+                (0, 10, HummockValue::put(iterator_test_value_of(0))),
+                (1, 10, HummockValue::put(iterator_test_value_of(1))),
+                (2, 10, HummockValue::delete()),
+            ],
+            sstable_store.clone(),
+        )
+        .await;
+
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+
+        let summary = sstable_iter.summarize().await.unwrap();
+        assert_eq!(summary.min_key, iterator_test_key_of_epoch(0, 10).encode());
+        assert_eq!(summary.max_key, iterator_test_key_of_epoch(2, 10).encode());
+        assert_eq!(summary.entry_count, 3);
+        assert_eq!(
+            summary.total_value_bytes,
+            (iterator_test_value_of(0).len() + iterator_test_value_of(1).len()) as u64
+        );
+        assert_eq!(summary.delete_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_table_iterator_value_len() {
+        let sstable_store = mock_sstable_store();
+        let sstable = gen_iterator_test_sstable_from_kv_pair(
+            0,
+            vec![
+                (0, 10, HummockValue::put(iterator_test_value_of(0))),
+                (1, 10, HummockValue::delete()),
+            ],
+            sstable_store.clone(),
+        )
+        .await;
+
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions::default()),
+        );
+        sstable_iter.rewind().await.unwrap();
+
+        assert!(sstable_iter.is_valid());
+        assert_eq!(sstable_iter.value_len(), sstable_iter.value().encoded_len());
+        sstable_iter.next().await.unwrap();
+
+        assert!(sstable_iter.is_valid());
+        assert!(sstable_iter.value().is_delete());
+        assert_eq!(sstable_iter.value_len(), sstable_iter.value().encoded_len());
+    }
+
     #[tokio::test]
     async fn test_table_seek() {
         let sstable_store = mock_sstable_store();
@@ -525,6 +798,7 @@ mod tests {
             Arc::new(SstableIteratorReadOptions {
                 cache_policy: CachePolicy::Fill(CachePriority::High),
                 must_iterated_end_user_key: None,
+                ..Default::default()
             }),
         );
         let mut cnt = 0;
@@ -539,4 +813,38 @@ mod tests {
         }
         assert_eq!(cnt, TEST_KEYS_COUNT);
     }
+
+    #[tokio::test]
+    async fn test_collect_value_size_histogram() {
+        let sstable_store = mock_sstable_store();
+        let sstable =
+            gen_default_test_sstable(default_builder_opt_for_test(), 0, sstable_store.clone())
+                .await;
+        let cache = create_small_table_cache();
+        let handle = cache.insert(0, 0, 1, Box::new(sstable), CachePriority::High);
+
+        let mut sstable_iter = SstableIterator::create(
+            handle,
+            sstable_store,
+            Arc::new(SstableIteratorReadOptions {
+                collect_histogram: true,
+                ..Default::default()
+            }),
+        );
+        sstable_iter.rewind().await.unwrap();
+
+        let mut expected = crate::monitor::ValueSizeHistogram::default();
+        while sstable_iter.is_valid() {
+            expected.record(sstable_iter.value_len());
+            sstable_iter.next().await.unwrap();
+        }
+
+        let mut stats = StoreLocalStatistic::default();
+        sstable_iter.collect_local_statistic(&mut stats);
+        let histogram = stats
+            .value_size_histogram
+            .expect("histogram should be collected when collect_histogram is set");
+        assert_eq!(histogram.buckets(), expected.buckets());
+        assert!(histogram.buckets().iter().any(|&count| count > 0));
+    }
 }
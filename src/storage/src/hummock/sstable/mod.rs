@@ -57,13 +57,14 @@ pub use delete_range_aggregator::{
 };
 pub use filter::FilterBuilder;
 pub use sstable_object_id_manager::*;
-pub use utils::CompressionAlgorithm;
+pub use utils::{CompressionAlgorithm, ZSTD_DEFAULT_COMPRESSION_LEVEL};
 use utils::{get_length_prefixed_slice, put_length_prefixed_slice};
 use xxhash_rust::{xxh32, xxh64};
 
 use self::delete_range_aggregator::{apply_event, CompactionDeleteRangeEvent};
 use self::utils::{xxhash64_checksum, xxhash64_verify};
 use super::{HummockError, HummockResult};
+use crate::hummock::utils::SstableLoadRetryOptions;
 use crate::hummock::CachePolicy;
 use crate::store::ReadOptions;
 
@@ -513,6 +514,35 @@ impl SstableMeta {
 pub struct SstableIteratorReadOptions {
     pub cache_policy: CachePolicy,
     pub must_iterated_end_user_key: Option<Bound<UserKey<KeyPayloadType>>>,
+    /// When set, [`ConcatIteratorInner`](crate::hummock::iterator::ConcatIteratorInner) asserts
+    /// after loading each table that its actual smallest/largest key is consistent with the
+    /// `key_range` used to binary-search it, returning a `HummockError` instead of silently
+    /// seeking into the wrong table. Off by default since it requires an extra check per table
+    /// switch.
+    pub verify_key_range: bool,
+    /// When set, [`SstableIterator`](crate::hummock::sstable::SstableIterator) recomputes and
+    /// checks each block's checksum (see [`Block::verify_checksum`]) right after fetching it,
+    /// returning a `HummockError` instead of silently reading corrupted data if a block that was
+    /// sitting in the block cache got corrupted after it was decoded. Off by default since it
+    /// requires an extra checksum pass over every block.
+    pub verify_checksum: bool,
+    /// Controls whether [`ConcatIteratorInner`](crate::hummock::iterator::ConcatIteratorInner)
+    /// retries a failed SST load. See [`SstableLoadRetryOptions`]. Defaults to no retries.
+    pub load_retry_options: SstableLoadRetryOptions,
+    /// When set, [`SstableIterator`](crate::hummock::sstable::SstableIterator) accumulates a
+    /// [`ValueSizeHistogram`](crate::monitor::ValueSizeHistogram) of the decoded value sizes it
+    /// observes into `StoreLocalStatistic::value_size_histogram`. Off by default, since most
+    /// scans only care about the existing block-level cache/IO statistics.
+    pub collect_histogram: bool,
+    /// When set, [`ConcatIteratorInner`](crate::hummock::iterator::ConcatIteratorInner)'s
+    /// `rewind` only records that a rewind happened, instead of eagerly loading the first table,
+    /// so a rewound iterator that never gets consumed doesn't pay for a load it didn't need. The
+    /// deferred load happens on the first subsequent `next`/`seek`. Because `is_valid`/`key`/
+    /// `value` are synchronous and can't perform the async load themselves, they must not be
+    /// called until a `next`/`seek` has run since the lazy `rewind` — doing so first would
+    /// report the iterator as invalid (or panic) even when it actually has data. Off by default,
+    /// since most callers rely on `is_valid` being answerable immediately after `rewind`.
+    pub lazy_rewind: bool,
 }
 
 impl SstableIteratorReadOptions {
@@ -520,6 +550,11 @@ impl SstableIteratorReadOptions {
         Self {
             cache_policy: read_options.cache_policy,
             must_iterated_end_user_key: None,
+            verify_key_range: false,
+            verify_checksum: false,
+            load_retry_options: SstableLoadRetryOptions::default(),
+            collect_histogram: false,
+            lazy_rewind: false,
         }
     }
 }
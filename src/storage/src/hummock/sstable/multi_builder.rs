@@ -21,6 +21,7 @@ use risingwave_common::hash::VirtualNode;
 use risingwave_hummock_sdk::key::{FullKey, UserKey};
 use risingwave_hummock_sdk::key_range::KeyRange;
 use risingwave_hummock_sdk::LocalSstableInfo;
+use tokio::sync::Semaphore;
 use tokio::task::JoinHandle;
 
 use super::{CompactionDeleteRanges, MonotonicDeleteEvent};
@@ -29,7 +30,7 @@ use crate::hummock::sstable::filter::FilterBuilder;
 use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::value::HummockValue;
 use crate::hummock::{
-    BatchUploadWriter, CachePolicy, HummockResult, MemoryLimiter, SstableBuilder,
+    BatchUploadWriter, CachePolicy, HummockError, HummockResult, MemoryLimiter, SstableBuilder,
     SstableBuilderOptions, SstableWriter, SstableWriterOptions, Xor16FilterBuilder,
 };
 use crate::monitor::CompactorMetrics;
@@ -48,6 +49,8 @@ pub struct SplitTableOutput {
     pub upload_join_handle: UploadJoinHandle,
 }
 
+type SealJoinHandle = JoinHandle<HummockResult<SplitTableOutput>>;
+
 /// A wrapper for [`SstableBuilder`] which automatically split key-value pairs into multiple tables,
 /// based on their target capacity set in options.
 ///
@@ -61,6 +64,18 @@ where
 
     sst_outputs: Vec<SplitTableOutput>,
 
+    /// Finishing tasks (block/bloom-filter encoding, meta building and upload) of sealed
+    /// builders that are running in the background, bounded by `concurrent_finish_limiter`. Only
+    /// populated when pipelining is enabled; otherwise `seal_current` finishes eagerly and pushes
+    /// straight to `sst_outputs`.
+    sealing_tasks: Vec<SealJoinHandle>,
+
+    /// When `Some`, `seal_current` hands a sealed builder's finishing work off to a background
+    /// task instead of awaiting it inline, so the next table can start accumulating key-value
+    /// pairs while the previous one is still being encoded and uploaded. The semaphore bounds how
+    /// many such finishing tasks may be in flight at once.
+    concurrent_finish_limiter: Option<Arc<Semaphore>>,
+
     current_builder: Option<SstableBuilder<F::Writer, F::Filter>>,
 
     /// Statistics.
@@ -80,11 +95,18 @@ where
     /// switch SST.
     largest_vnode_in_current_partition: usize,
     last_vnode: usize,
+    /// When `true` (the default), a builder that reaches capacity is never sealed until the
+    /// next distinct user key, even if that means overshooting capacity somewhat. This preserves
+    /// the invariant, required by some compaction/read paths, that all versions of a user key
+    /// live in a single SST. When `false`, a full builder may be sealed mid version-group.
+    split_on_user_key_boundary: bool,
 }
 
 impl<F> CapacitySplitTableBuilder<F>
 where
     F: TableBuilderFactory,
+    F::Writer: 'static,
+    F::Filter: 'static,
 {
     /// Creates a new [`CapacitySplitTableBuilder`] using given configuration generator.
     #[allow(clippy::too_many_arguments)]
@@ -115,6 +137,8 @@ where
         Self {
             builder_factory,
             sst_outputs: Vec::new(),
+            sealing_tasks: Vec::new(),
+            concurrent_finish_limiter: None,
             current_builder: None,
             compactor_metrics,
             task_progress,
@@ -127,6 +151,7 @@ where
             split_weight_by_vnode,
             largest_vnode_in_current_partition: VirtualNode::MAX.to_index(),
             last_vnode: 0,
+            split_on_user_key_boundary: true,
         }
     }
 
@@ -134,6 +159,8 @@ where
         Self {
             builder_factory,
             sst_outputs: Vec::new(),
+            sealing_tasks: Vec::new(),
+            concurrent_finish_limiter: None,
             current_builder: None,
             compactor_metrics: Arc::new(CompactorMetrics::unused()),
             task_progress: None,
@@ -146,17 +173,38 @@ where
             split_weight_by_vnode: 0,
             largest_vnode_in_current_partition: VirtualNode::MAX.to_index(),
             last_vnode: 0,
+            split_on_user_key_boundary: true,
         }
     }
 
-    /// Returns the number of [`SstableBuilder`]s.
+    /// Enables pipelined finishing: once this call returns, sealing a builder (e.g. because it
+    /// reached capacity) hands its encode/bloom-filter/upload work off to a background task
+    /// instead of blocking the caller, so the next table can start accumulating immediately. At
+    /// most `max_concurrent_finish` such tasks may run at once; further seals block until one
+    /// completes.
+    pub fn with_concurrent_finish(mut self, max_concurrent_finish: usize) -> Self {
+        self.concurrent_finish_limiter = Some(Arc::new(Semaphore::new(max_concurrent_finish)));
+        self
+    }
+
+    /// Overrides whether a capacity-triggered seal must wait for the next distinct user key
+    /// (the default). Pass `false` to allow a full builder to be sealed in the middle of a user
+    /// key's version group, giving up the one-SST-per-user-key invariant in exchange for exact
+    /// capacity enforcement.
+    pub fn with_split_on_user_key_boundary(mut self, split_on_user_key_boundary: bool) -> Self {
+        self.split_on_user_key_boundary = split_on_user_key_boundary;
+        self
+    }
+
+    /// Returns the number of [`SstableBuilder`]s, including ones still finishing in the
+    /// background.
     pub fn len(&self) -> usize {
-        self.sst_outputs.len() + self.current_builder.is_some() as usize
+        self.sst_outputs.len() + self.sealing_tasks.len() + self.current_builder.is_some() as usize
     }
 
     /// Returns true if no builder is created.
     pub fn is_empty(&self) -> bool {
-        self.sst_outputs.is_empty() && self.current_builder.is_none()
+        self.sst_outputs.is_empty() && self.sealing_tasks.is_empty() && self.current_builder.is_none()
     }
 
     pub async fn add_full_key_for_test(
@@ -170,11 +218,10 @@ where
 
     /// Adds a key-value pair to the underlying builders.
     ///
-    /// If `allow_split` and the current builder reaches its capacity, this function will create a
-    /// new one with the configuration generated by the closure provided earlier.
-    ///
-    /// Note that in some cases like compaction of the same user key, automatic splitting is not
-    /// allowed, where `allow_split` should be `false`.
+    /// If the current builder reaches its capacity, this function will create a new one with the
+    /// configuration generated by the closure provided earlier. Unless disabled via
+    /// [`Self::with_split_on_user_key_boundary`], this only happens on `is_new_user_key`, i.e. a
+    /// capacity-triggered split never lands in the middle of a user key's version group.
     pub async fn add_full_key(
         &mut self,
         full_key: FullKey<&[u8]>,
@@ -227,13 +274,13 @@ where
         // `current_builder` itself is required to be `Sync`, which is unnecessary.
         let mut need_seal_current = false;
         if let Some(builder) = self.current_builder.as_ref() {
-            if is_new_user_key {
-                if switch_builder {
-                    need_seal_current = true;
-                } else if builder.reach_capacity() {
-                    need_seal_current = self.split_weight_by_vnode == 0
-                        || (self.is_target_level_l0_or_lbase && vnode_changed);
-                }
+            if is_new_user_key && switch_builder {
+                need_seal_current = true;
+            } else if (is_new_user_key || !self.split_on_user_key_boundary)
+                && builder.reach_capacity()
+            {
+                need_seal_current = self.split_weight_by_vnode == 0
+                    || (self.is_target_level_l0_or_lbase && vnode_changed);
             }
         }
         if need_seal_current {
@@ -262,58 +309,48 @@ where
     ///
     /// If there's no builder created, or current one is already sealed before, then this function
     /// will be no-op.
+    ///
+    /// If pipelining was enabled via [`Self::with_concurrent_finish`], the sealed builder's
+    /// finishing work (block/bloom-filter encoding, meta building and upload) runs on a
+    /// background task and this call returns as soon as a slot is available, without waiting for
+    /// that work to complete. Otherwise it finishes the builder inline, as before.
     pub async fn seal_current(
         &mut self,
         monotonic_deletes: Vec<MonotonicDeleteEvent>,
     ) -> HummockResult<()> {
         if let Some(mut builder) = self.current_builder.take() {
             builder.add_monotonic_deletes(monotonic_deletes);
-            let builder_output = builder.finish().await?;
-            {
-                // report
-                if let Some(progress) = &self.task_progress {
-                    progress.inc_ssts_sealed();
-                }
-
-                if builder_output.bloom_filter_size != 0 {
-                    self.compactor_metrics
-                        .sstable_bloom_filter_size
-                        .observe(builder_output.bloom_filter_size as _);
-                }
-
-                if builder_output.sst_info.file_size() != 0 {
-                    self.compactor_metrics
-                        .sstable_file_size
-                        .observe(builder_output.sst_info.file_size() as _);
-                }
-
-                if builder_output.avg_key_size != 0 {
-                    self.compactor_metrics
-                        .sstable_avg_key_size
-                        .observe(builder_output.avg_key_size as _);
-                }
-
-                if builder_output.avg_value_size != 0 {
-                    self.compactor_metrics
-                        .sstable_avg_value_size
-                        .observe(builder_output.avg_value_size as _);
-                }
-
-                if builder_output.epoch_count != 0 {
-                    self.compactor_metrics
-                        .sstable_distinct_epoch_count
-                        .observe(builder_output.epoch_count as _);
-                }
+            if let Some(limiter) = self.concurrent_finish_limiter.clone() {
+                let permit = limiter.acquire_owned().await.unwrap();
+                let compactor_metrics = self.compactor_metrics.clone();
+                let task_progress = self.task_progress.clone();
+                self.sealing_tasks.push(tokio::spawn(async move {
+                    let builder_output = builder.finish().await?;
+                    report_sst_metrics(&compactor_metrics, task_progress.as_deref(), &builder_output);
+                    drop(permit);
+                    Ok(SplitTableOutput {
+                        upload_join_handle: builder_output.writer_output,
+                        sst_info: builder_output.sst_info,
+                    })
+                }));
+            } else {
+                let builder_output = builder.finish().await?;
+                report_sst_metrics(
+                    &self.compactor_metrics,
+                    self.task_progress.as_deref(),
+                    &builder_output,
+                );
+                self.sst_outputs.push(SplitTableOutput {
+                    upload_join_handle: builder_output.writer_output,
+                    sst_info: builder_output.sst_info,
+                });
             }
-            self.sst_outputs.push(SplitTableOutput {
-                upload_join_handle: builder_output.writer_output,
-                sst_info: builder_output.sst_info,
-            });
         }
         Ok(())
     }
 
-    /// Finalizes all the tables to be ids, blocks and metadata.
+    /// Finalizes all the tables to be ids, blocks and metadata. Awaits any finishing tasks still
+    /// running in the background when pipelining is enabled.
     pub async fn finish(mut self) -> HummockResult<Vec<SplitTableOutput>> {
         let largest_user_key = if self.key_range.right.is_empty() {
             UserKey::default()
@@ -333,10 +370,55 @@ where
             self.current_builder = Some(builder);
         }
         self.seal_current(monotonic_deletes).await?;
+        for task in self.sealing_tasks {
+            self.sst_outputs.push(task.await.map_err(HummockError::other)??);
+        }
         Ok(self.sst_outputs)
     }
 }
 
+/// Reports per-SST metrics for a just-finished builder. Shared by the eager and pipelined
+/// `seal_current` paths.
+fn report_sst_metrics<WO>(
+    compactor_metrics: &CompactorMetrics,
+    task_progress: Option<&TaskProgress>,
+    builder_output: &crate::hummock::SstableBuilderOutput<WO>,
+) {
+    if let Some(progress) = task_progress {
+        progress.inc_ssts_sealed();
+    }
+
+    if builder_output.bloom_filter_size != 0 {
+        compactor_metrics
+            .sstable_bloom_filter_size
+            .observe(builder_output.bloom_filter_size as _);
+    }
+
+    if builder_output.sst_info.file_size() != 0 {
+        compactor_metrics
+            .sstable_file_size
+            .observe(builder_output.sst_info.file_size() as _);
+    }
+
+    if builder_output.avg_key_size != 0 {
+        compactor_metrics
+            .sstable_avg_key_size
+            .observe(builder_output.avg_key_size as _);
+    }
+
+    if builder_output.avg_value_size != 0 {
+        compactor_metrics
+            .sstable_avg_value_size
+            .observe(builder_output.avg_value_size as _);
+    }
+
+    if builder_output.epoch_count != 0 {
+        compactor_metrics
+            .sstable_distinct_epoch_count
+            .observe(builder_output.epoch_count as _);
+    }
+}
+
 /// Used for unit tests and benchmarks.
 pub struct LocalTableBuilderFactory {
     next_id: AtomicU64,
@@ -411,6 +493,9 @@ mod tests {
             restart_interval: DEFAULT_RESTART_INTERVAL,
             bloom_false_positive: 0.1,
             compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
         };
         let builder_factory = LocalTableBuilderFactory::new(1001, mock_sstable_store(), opts);
         let builder = CapacitySplitTableBuilder::for_test(builder_factory);
@@ -428,6 +513,9 @@ mod tests {
             restart_interval: DEFAULT_RESTART_INTERVAL,
             bloom_false_positive: 0.1,
             compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
         };
         let builder_factory = LocalTableBuilderFactory::new(1001, mock_sstable_store(), opts);
         let mut builder = CapacitySplitTableBuilder::for_test(builder_factory);
@@ -450,6 +538,122 @@ mod tests {
         assert!(results.len() > 1);
     }
 
+    #[tokio::test]
+    async fn test_lots_of_tables_pipelined() {
+        let block_size = 1 << 10;
+        let table_capacity = 4 * block_size;
+        let opts = SstableBuilderOptions {
+            capacity: table_capacity,
+            block_capacity: block_size,
+            restart_interval: DEFAULT_RESTART_INTERVAL,
+            bloom_false_positive: 0.1,
+            compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
+        };
+        let builder_factory = LocalTableBuilderFactory::new(1001, mock_sstable_store(), opts);
+        let mut builder =
+            CapacitySplitTableBuilder::for_test(builder_factory).with_concurrent_finish(2);
+
+        for i in 0..table_capacity {
+            builder
+                .add_full_key_for_test(
+                    FullKey::from_user_key(
+                        test_user_key_of(i).as_ref(),
+                        (table_capacity - i) as u64,
+                    ),
+                    HummockValue::put(b"value"),
+                    true,
+                )
+                .await
+                .unwrap();
+            // Builders are sealed as finishing tasks instead of being pushed straight to
+            // `sst_outputs`, but `len` must still account for them.
+            assert_eq!(builder.len(), builder.sealing_tasks.len() + 1);
+        }
+
+        let results = builder.finish().await.unwrap();
+        assert!(results.len() > 1);
+    }
+
+    #[tokio::test]
+    async fn test_split_by_max_key_count() {
+        let opts = SstableBuilderOptions {
+            // Large enough that capacity alone would never trigger a split.
+            capacity: 64 * (1 << 20),
+            block_capacity: 1 << 20,
+            restart_interval: DEFAULT_RESTART_INTERVAL,
+            bloom_false_positive: 0.1,
+            compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: 10,
+            build_bloom_filter: true,
+            adaptive_restart: false,
+        };
+        let builder_factory = LocalTableBuilderFactory::new(1001, mock_sstable_store(), opts);
+        let mut builder = CapacitySplitTableBuilder::for_test(builder_factory);
+
+        for i in 0..100 {
+            builder
+                .add_full_key_for_test(
+                    FullKey::from_user_key(test_user_key_of(i).as_ref(), (100 - i) as u64),
+                    HummockValue::put(b"v"),
+                    true,
+                )
+                .await
+                .unwrap();
+        }
+
+        let results = builder.finish().await.unwrap();
+        assert!(results.len() >= 10);
+    }
+
+    #[tokio::test]
+    async fn test_split_on_user_key_boundary_defers_capacity_split() {
+        let opts = SstableBuilderOptions {
+            capacity: 64 * (1 << 20),
+            block_capacity: 1 << 20,
+            restart_interval: DEFAULT_RESTART_INTERVAL,
+            bloom_false_positive: 0.1,
+            compression_algorithm: CompressionAlgorithm::None,
+            // Small enough that a single user key's version group blows past it.
+            max_sst_key_count: 5,
+            build_bloom_filter: true,
+            adaptive_restart: false,
+        };
+        let builder_factory = LocalTableBuilderFactory::new(1001, mock_sstable_store(), opts);
+        let mut builder = CapacitySplitTableBuilder::for_test(builder_factory);
+
+        // 10 versions of the same user key, well past `max_sst_key_count`.
+        for epoch in (1..=10).rev() {
+            builder
+                .add_full_key_for_test(
+                    FullKey::from_user_key(test_user_key_of(1).as_ref(), epoch),
+                    HummockValue::put(b"v"),
+                    epoch == 10,
+                )
+                .await
+                .unwrap();
+        }
+        // Capacity was reached mid-group, but since none of those adds were a new user key, no
+        // split should have happened yet.
+        assert_eq!(builder.len(), 1);
+
+        // The next distinct user key is where the deferred split finally lands.
+        builder
+            .add_full_key_for_test(
+                FullKey::from_user_key(test_user_key_of(2).as_ref(), 1),
+                HummockValue::put(b"v"),
+                true,
+            )
+            .await
+            .unwrap();
+        assert_eq!(builder.len(), 2);
+
+        let results = builder.finish().await.unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_table_seal() {
         let opts = default_builder_opt_for_test();
@@ -600,6 +804,9 @@ mod tests {
             restart_interval: DEFAULT_RESTART_INTERVAL,
             bloom_false_positive: 0.1,
             compression_algorithm: CompressionAlgorithm::None,
+            max_sst_key_count: u64::MAX,
+            build_bloom_filter: true,
+            adaptive_restart: false,
         };
         let table_id = TableId::new(1);
         let mut builder = CompactionDeleteRangesBuilder::default();
@@ -83,11 +83,18 @@ pub fn get_length_prefixed_slice(buf: &mut &[u8]) -> Vec<u8> {
     v
 }
 
+/// Default zstd compression level, matching the level RisingWave has historically hardcoded for
+/// `Zstd`-compressed blocks.
+pub const ZSTD_DEFAULT_COMPRESSION_LEVEL: i32 = 4;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CompressionAlgorithm {
     None,
     Lz4,
-    Zstd,
+    /// The `level` only affects how hard the encoder works to compress a block; it is not
+    /// needed to decompress the resulting zstd frame, so it need not be recovered when decoding
+    /// a `CompressionAlgorithm` read back from an sstable.
+    Zstd { level: i32 },
 }
 
 impl CompressionAlgorithm {
@@ -95,7 +102,7 @@ impl CompressionAlgorithm {
         let v = match self {
             Self::None => 0,
             Self::Lz4 => 1,
-            Self::Zstd => 2,
+            Self::Zstd { .. } => 2,
         };
         buf.put_u8(v);
     }
@@ -104,7 +111,9 @@ impl CompressionAlgorithm {
         match buf.get_u8() {
             0 => Ok(Self::None),
             1 => Ok(Self::Lz4),
-            2 => Ok(Self::Zstd),
+            2 => Ok(Self::Zstd {
+                level: ZSTD_DEFAULT_COMPRESSION_LEVEL,
+            }),
             _ => Err(HummockError::decode_error(
                 "not valid compression algorithm",
             )),
@@ -117,7 +126,7 @@ impl From<CompressionAlgorithm> for u8 {
         match ca {
             CompressionAlgorithm::None => 0,
             CompressionAlgorithm::Lz4 => 1,
-            CompressionAlgorithm::Zstd => 2,
+            CompressionAlgorithm::Zstd { .. } => 2,
         }
     }
 }
@@ -127,7 +136,7 @@ impl From<CompressionAlgorithm> for u64 {
         match ca {
             CompressionAlgorithm::None => 0,
             CompressionAlgorithm::Lz4 => 1,
-            CompressionAlgorithm::Zstd => 2,
+            CompressionAlgorithm::Zstd { .. } => 2,
         }
     }
 }
@@ -139,7 +148,9 @@ impl TryFrom<u8> for CompressionAlgorithm {
         match v {
             0 => Ok(Self::None),
             1 => Ok(Self::Lz4),
-            2 => Ok(Self::Zstd),
+            2 => Ok(Self::Zstd {
+                level: ZSTD_DEFAULT_COMPRESSION_LEVEL,
+            }),
             _ => Err(HummockError::decode_error(
                 "not valid compression algorithm",
             )),
@@ -139,12 +139,32 @@ impl From<CachePolicy> for TracedCachePolicy {
     }
 }
 
+/// Resolves the object-store path prefix under which a given SST's data and meta live.
+/// `object_id` is the only identifier available at every call site that touches a path (block
+/// reads, deletes, uploads, ...), so implementations that want per-tenant layouts must derive the
+/// tenant from `object_id` itself, e.g. by keeping their own `object_id` -> tenant registry
+/// populated when the SST is created. The default [`FlatPathResolver`] keeps every SST under the
+/// store's configured `path`, matching the historical behaviour.
+pub trait SstablePathResolver: Send + Sync {
+    fn resolve(&self, base_path: &str, object_id: HummockSstableObjectId) -> String;
+}
+
+/// The original flat layout, where every SST lives directly under the store's configured `path`.
+pub struct FlatPathResolver;
+
+impl SstablePathResolver for FlatPathResolver {
+    fn resolve(&self, base_path: &str, _object_id: HummockSstableObjectId) -> String {
+        base_path.to_string()
+    }
+}
+
 pub struct SstableStore {
     path: String,
     store: ObjectStoreRef,
     block_cache: BlockCache,
     meta_cache: Arc<LruCache<HummockSstableObjectId, Box<Sstable>>>,
     tiered_cache: TieredCache<(HummockSstableObjectId, u64), Box<Block>>,
+    path_resolver: Arc<dyn SstablePathResolver>,
 }
 
 impl SstableStore {
@@ -178,9 +198,17 @@ impl SstableStore {
             ),
             meta_cache,
             tiered_cache,
+            path_resolver: Arc::new(FlatPathResolver),
         }
     }
 
+    /// Overrides the default flat path layout with `resolver`, e.g. for a multi-tenant deployment
+    /// that lays out SSTs per tenant directory for lifecycle and access control.
+    pub fn with_path_resolver(mut self, resolver: Arc<dyn SstablePathResolver>) -> Self {
+        self.path_resolver = resolver;
+        self
+    }
+
     /// For compactor, we do not need a high concurrency load for cache. Instead, we need the cache
     ///  can be evict more effective.
     pub fn for_compactor(
@@ -197,6 +225,7 @@ impl SstableStore {
             block_cache: BlockCache::new(block_cache_capacity, 0, 0),
             meta_cache,
             tiered_cache,
+            path_resolver: Arc::new(FlatPathResolver),
         }
     }
 
@@ -341,10 +370,8 @@ impl SstableStore {
 
     pub fn get_sst_data_path(&self, object_id: HummockSstableObjectId) -> String {
         let obj_prefix = self.store.get_object_prefix(object_id);
-        format!(
-            "{}/{}{}.{}",
-            self.path, obj_prefix, object_id, OBJECT_SUFFIX
-        )
+        let path = self.path_resolver.resolve(&self.path, object_id);
+        format!("{}/{}{}.{}", path, obj_prefix, object_id, OBJECT_SUFFIX)
     }
 
     pub fn get_object_id_from_path(&self, path: &str) -> HummockSstableObjectId {
@@ -446,6 +473,8 @@ impl SstableStore {
         )
     }
 
+    /// Lists SSTs under the store's default `path`. Note this does not see SSTs that a custom
+    /// `path_resolver` has routed elsewhere; listing those is the resolver owner's responsibility.
     pub async fn list_ssts_from_object_store(&self) -> HummockResult<Vec<ObjectMetadata>> {
         self.store
             .list(&format!("{}/", self.path))
@@ -492,6 +521,36 @@ impl SstableStore {
         self.meta_cache.get_memory_usage() as u64
     }
 
+    /// Sequentially loads every block of each of `ssts` into the block cache with
+    /// [`CachePolicy::Fill`], returning the total number of bytes warmed. Meant to replace the
+    /// ad hoc "scan the whole table to warm it up" dance that benches and tests otherwise
+    /// reimplement, and to let operators pre-load hot SSTs after a restart.
+    ///
+    /// Takes the full [`SstableInfo`]s rather than bare object ids because loading an SST's meta
+    /// (a prerequisite for loading its blocks) requires the offset and size recorded there.
+    pub async fn warm_up(
+        &self,
+        ssts: &[SstableInfo],
+        stats: &mut StoreLocalStatistic,
+    ) -> HummockResult<u64> {
+        let mut bytes = 0;
+        for sst_info in ssts {
+            let sst = self.sstable(sst_info, stats).await?;
+            for block_index in 0..sst.value().block_count() {
+                let block = self
+                    .get(
+                        sst.value(),
+                        block_index,
+                        CachePolicy::Fill(CachePriority::High),
+                        stats,
+                    )
+                    .await?;
+                bytes += block.size() as u64;
+            }
+        }
+        Ok(bytes)
+    }
+
     pub async fn get_stream(
         &self,
         sst: &Sstable,
@@ -932,18 +991,20 @@ mod tests {
     use std::sync::Arc;
 
     use risingwave_hummock_sdk::HummockSstableObjectId;
+    use risingwave_object_store::object::{InMemObjectStore, ObjectStore, ObjectStoreImpl};
     use risingwave_pb::hummock::SstableInfo;
 
     use super::{SstableStoreRef, SstableWriterOptions};
     use crate::hummock::iterator::test_utils::{iterator_test_key_of, mock_sstable_store};
     use crate::hummock::iterator::HummockIterator;
     use crate::hummock::sstable::SstableIteratorReadOptions;
+    use crate::hummock::sstable_store::SstablePathResolver;
     use crate::hummock::test_utils::{
         default_builder_opt_for_test, gen_test_sstable_data, put_sst,
     };
     use crate::hummock::value::HummockValue;
-    use crate::hummock::{CachePolicy, SstableIterator, SstableMeta};
-    use crate::monitor::StoreLocalStatistic;
+    use crate::hummock::{CachePolicy, SstableIterator, SstableMeta, TieredCache};
+    use crate::monitor::{ObjectStoreMetrics, StoreLocalStatistic};
 
     const SST_ID: HummockSstableObjectId = 1;
 
@@ -1037,6 +1098,54 @@ mod tests {
         validate_sst(sstable_store, &info, meta, x_range).await;
     }
 
+    #[tokio::test]
+    async fn test_warm_up() {
+        let sstable_store = mock_sstable_store();
+
+        let mut infos = vec![];
+        for (sst_id, x_range) in [(10, 0..100), (11, 100..200)] {
+            let (data, meta) = gen_test_sstable_data(
+                default_builder_opt_for_test(),
+                x_range
+                    .clone()
+                    .map(|x| (iterator_test_key_of(x), get_hummock_value(x))),
+            )
+            .await;
+            let writer_opts = SstableWriterOptions {
+                capacity_hint: None,
+                tracker: None,
+                policy: CachePolicy::Disable,
+            };
+            let info = put_sst(sst_id, data, meta, sstable_store.clone(), writer_opts)
+                .await
+                .unwrap();
+            infos.push(info);
+        }
+
+        let mut warm_up_stats = StoreLocalStatistic::default();
+        let bytes = sstable_store
+            .warm_up(&infos, &mut warm_up_stats)
+            .await
+            .unwrap();
+        assert!(bytes > 0);
+
+        // The blocks are already in the cache, so scanning both SSTs should not miss.
+        let mut scan_stats = StoreLocalStatistic::default();
+        for info in &infos {
+            let holder = sstable_store.sstable(info, &mut scan_stats).await.unwrap();
+            let mut iter = SstableIterator::new(
+                holder,
+                sstable_store.clone(),
+                Arc::new(SstableIteratorReadOptions::default()),
+            );
+            iter.rewind().await.unwrap();
+            while iter.is_valid() {
+                iter.next().await.unwrap();
+            }
+        }
+        assert_eq!(scan_stats.cache_data_block_miss, 0);
+    }
+
     #[test]
     fn test_basic() {
         let sstable_store = mock_sstable_store();
@@ -1045,4 +1154,83 @@ mod tests {
         assert_eq!(data_path, "test/123.data");
         assert_eq!(sstable_store.get_object_id_from_path(&data_path), object_id);
     }
+
+    /// A resolver standing in for a multi-tenant deployment: every SST created through this
+    /// resolver lives under its own `table-{id}` subdirectory instead of the flat base path.
+    struct TablePrefixPathResolver {
+        table_id: u32,
+    }
+
+    impl SstablePathResolver for TablePrefixPathResolver {
+        fn resolve(&self, base_path: &str, _object_id: HummockSstableObjectId) -> String {
+            format!("{}/table-{}", base_path, self.table_id)
+        }
+    }
+
+    fn mock_sstable_store_with_resolver(table_id: u32) -> SstableStoreRef {
+        Arc::new(
+            SstableStore::new(
+                Arc::new(ObjectStoreImpl::InMem(
+                    InMemObjectStore::new().monitored(Arc::new(ObjectStoreMetrics::unused())),
+                )),
+                "test".to_string(),
+                64 << 20,
+                64 << 20,
+                0,
+                TieredCache::none(),
+            )
+            .with_path_resolver(Arc::new(TablePrefixPathResolver { table_id })),
+        )
+    }
+
+    #[test]
+    fn test_path_resolver_prefixes_by_table_id() {
+        let sstable_store = mock_sstable_store();
+        let object_id = 456;
+        assert_eq!(sstable_store.get_sst_data_path(object_id), "test/456.data");
+
+        let sstable_store = mock_sstable_store_with_resolver(42);
+        assert_eq!(
+            sstable_store.get_sst_data_path(object_id),
+            "test/table-42/456.data"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_path_resolver_put_get_round_trip() {
+        let sstable_store = mock_sstable_store_with_resolver(42);
+        let x_range = 0..100;
+        let (data, meta) = gen_test_sstable_data(
+            default_builder_opt_for_test(),
+            x_range
+                .clone()
+                .map(|x| (iterator_test_key_of(x), get_hummock_value(x))),
+        )
+        .await;
+        let writer_opts = SstableWriterOptions {
+            capacity_hint: None,
+            tracker: None,
+            policy: CachePolicy::Disable,
+        };
+        let info = put_sst(
+            SST_ID,
+            data.clone(),
+            meta.clone(),
+            sstable_store.clone(),
+            writer_opts,
+        )
+        .await
+        .unwrap();
+
+        // The SST was written to the resolver-chosen path, not the flat default path.
+        assert!(sstable_store
+            .store()
+            .list("test/table-42/")
+            .await
+            .unwrap()
+            .into_iter()
+            .any(|o| o.key == sstable_store.get_sst_data_path(SST_ID)));
+
+        validate_sst(sstable_store, &info, meta, x_range).await;
+    }
 }
@@ -128,6 +128,9 @@ pub fn default_builder_opt_for_test() -> SstableBuilderOptions {
         restart_interval: DEFAULT_RESTART_INTERVAL,
         bloom_false_positive: 0.1,
         compression_algorithm: CompressionAlgorithm::None,
+        max_sst_key_count: u64::MAX,
+        build_bloom_filter: true,
+        adaptive_restart: false,
     }
 }
 
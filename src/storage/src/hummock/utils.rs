@@ -486,6 +486,49 @@ fn validate_delete_range(left: &Bound<Bytes>, right: &Bound<Bytes>) -> bool {
     }
 }
 
+/// Configures bounded retry-with-backoff for a single SST-load call, used by
+/// `ConcatIteratorInner` and `ConcatSstableIterator` when the underlying object store is flaky.
+/// `max_retries: 0` (the default) preserves the original behaviour of failing on the first error.
+#[derive(Clone, Copy, Debug)]
+pub struct SstableLoadRetryOptions {
+    pub max_retries: usize,
+    pub base_delay: std::time::Duration,
+}
+
+impl Default for SstableLoadRetryOptions {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: std::time::Duration::from_millis(100),
+        }
+    }
+}
+
+/// Runs `load` and, if it fails with [`HummockError::is_object_io_error`], retries up to
+/// `options.max_retries` times with exponentially increasing delay (`base_delay * 2^attempt`)
+/// before giving up. Errors that are not object-store IO errors (e.g. decode errors, key-range
+/// mismatches) are returned immediately without retrying, since retrying cannot fix them.
+pub(crate) async fn retry_sstable_load<F, Fut, T>(
+    options: &SstableLoadRetryOptions,
+    mut load: F,
+) -> HummockResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = HummockResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match load().await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_object_io_error() && attempt < options.max_retries => {
+                tokio::time::sleep(options.base_delay * 2u32.pow(attempt as u32)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 pub(crate) fn filter_with_delete_range<'a>(
     kv_iter: impl Iterator<Item = (Bytes, KeyOp)> + 'a,
     mut delete_ranges_iter: impl Iterator<Item = &'a (Bound<Bytes>, Bound<Bytes>)> + 'a,
@@ -579,4 +622,56 @@ mod tests {
         drop(tracker3);
         assert_eq!(0, memory_limiter.get_memory_usage());
     }
+
+    #[tokio::test]
+    async fn test_retry_sstable_load_recovers_from_transient_errors() {
+        use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+        use crate::hummock::utils::{retry_sstable_load, SstableLoadRetryOptions};
+        use crate::hummock::HummockError;
+
+        let options = SstableLoadRetryOptions {
+            max_retries: 2,
+            base_delay: std::time::Duration::from_millis(1),
+        };
+        let attempts = AtomicUsize::new(0);
+        let result: Result<_, HummockError> = retry_sstable_load(&options, || {
+            let attempt = attempts.fetch_add(1, AtomicOrdering::SeqCst);
+            async move {
+                if attempt < 2 {
+                    Err(HummockError::object_io_error(
+                        risingwave_object_store::object::ObjectError::internal(
+                            "transient object store error",
+                        ),
+                    ))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(attempts.load(AtomicOrdering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_sstable_load_gives_up_on_non_io_error() {
+        use crate::hummock::utils::{retry_sstable_load, SstableLoadRetryOptions};
+        use crate::hummock::HummockError;
+
+        let options = SstableLoadRetryOptions {
+            max_retries: 5,
+            base_delay: std::time::Duration::from_millis(1),
+        };
+        let mut calls = 0;
+        let result: Result<(), HummockError> = retry_sstable_load(&options, || {
+            calls += 1;
+            async { Err(HummockError::decode_error("corrupt sstable meta")) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
 }
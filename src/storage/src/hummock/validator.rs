@@ -18,14 +18,99 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use risingwave_hummock_sdk::key::FullKey;
-use risingwave_pb::hummock::ValidationTask;
+use risingwave_hummock_sdk::key_range::KeyRange;
+use risingwave_pb::hummock::{SstableInfo, ValidationTask};
 
 use crate::hummock::iterator::HummockIterator;
 use crate::hummock::sstable::SstableIteratorReadOptions;
 use crate::hummock::sstable_store::SstableStoreRef;
-use crate::hummock::{CachePolicy, SstableIterator};
+use crate::hummock::{CachePolicy, HummockError, HummockResult, SstableIterator};
 use crate::monitor::StoreLocalStatistic;
 
+/// Rebuilds `info`'s true key range directly from the SST's contents, for repairing a manifest
+/// entry whose `key_range` has drifted from reality. Returns the correct inclusive `[left,
+/// right]` range; does not mutate `info`.
+pub async fn recompute_key_range(
+    info: &SstableInfo,
+    sstable_store: &SstableStoreRef,
+    stats: &mut StoreLocalStatistic,
+) -> HummockResult<KeyRange> {
+    let holder = sstable_store.sstable(info, stats).await?;
+    let mut iter = SstableIterator::new(
+        holder,
+        sstable_store.clone(),
+        Arc::new(SstableIteratorReadOptions {
+            cache_policy: CachePolicy::NotFill,
+            must_iterated_end_user_key: None,
+            ..Default::default()
+        }),
+    );
+    iter.rewind().await?;
+    let left = iter.key().encode();
+    let mut right = left.clone();
+    while iter.is_valid() {
+        right = iter.key().encode();
+        iter.next().await?;
+    }
+    iter.collect_local_statistic(stats);
+    Ok(KeyRange::new(left.into(), right.into()))
+}
+
+/// Scans `ssts` — assumed to be one compaction task's ordered, non-overlapping output tables, in
+/// order — and checks that keys are strictly increasing and that no two entries share the same
+/// (user key, epoch) pair, including across a boundary between two tables. Returns a descriptive
+/// `HummockError` on the first violation found instead of panicking, so it is cheap to run as a
+/// guardrail right after [`Compactor::compact_and_build_sst`](super::compactor::Compactor::compact_and_build_sst)
+/// in debug/CI builds without taking down the compactor on a real bug.
+pub async fn validate_output(
+    ssts: &[SstableInfo],
+    sstable_store: &SstableStoreRef,
+    stats: &mut StoreLocalStatistic,
+) -> HummockResult<()> {
+    let mut previous_key: Option<FullKey<Vec<u8>>> = None;
+    for sst in ssts {
+        let holder = sstable_store.sstable(sst, stats).await?;
+        let mut iter = SstableIterator::new(
+            holder,
+            sstable_store.clone(),
+            Arc::new(SstableIteratorReadOptions {
+                cache_policy: CachePolicy::NotFill,
+                ..Default::default()
+            }),
+        );
+        iter.rewind().await?;
+        while iter.is_valid() {
+            let current_key = iter.key().to_vec();
+            if let Some(previous_key) = &previous_key {
+                match previous_key.cmp(&current_key) {
+                    cmp::Ordering::Less => {}
+                    cmp::Ordering::Equal => {
+                        return Err(HummockError::other(format!(
+                            "compaction output validation failed: duplicate (user key, epoch) \
+                             {:x?} in SST {}",
+                            current_key,
+                            sst.get_object_id()
+                        )));
+                    }
+                    cmp::Ordering::Greater => {
+                        return Err(HummockError::other(format!(
+                            "compaction output validation failed: unsorted keys in SST {}, \
+                             expected {:x?} < {:x?}",
+                            sst.get_object_id(),
+                            previous_key,
+                            current_key
+                        )));
+                    }
+                }
+            }
+            previous_key = Some(current_key);
+            iter.next().await?;
+        }
+        iter.collect_local_statistic(stats);
+    }
+    Ok(())
+}
+
 /// Validate SSTs in terms of Ordered, Locally unique and Globally unique.
 ///
 /// See `src/storage/src/hummock/state_store.rs`
@@ -61,6 +146,7 @@ pub async fn validate_ssts(task: ValidationTask, sstable_store: SstableStoreRef)
             Arc::new(SstableIteratorReadOptions {
                 cache_policy: CachePolicy::NotFill,
                 must_iterated_end_user_key: None,
+                ..Default::default()
             }),
         );
         let mut previous_key: Option<FullKey<Vec<u8>>> = None;
@@ -114,3 +200,97 @@ pub async fn validate_ssts(task: ValidationTask, sstable_store: SstableStoreRef)
         unused.ignore();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_hummock_sdk::HummockSstableObjectId;
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::mock_sstable_store;
+    use crate::hummock::test_utils::{
+        default_builder_opt_for_test, gen_test_sstable_and_info, test_key_of, test_value_of,
+        TEST_KEYS_COUNT,
+    };
+    use crate::hummock::HummockValue;
+
+    #[tokio::test]
+    async fn test_recompute_key_range() {
+        let sstable_store = mock_sstable_store();
+        let (_sst, mut info) = gen_test_sstable_and_info(
+            default_builder_opt_for_test(),
+            1 as HummockSstableObjectId,
+            (0..TEST_KEYS_COUNT).map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+            sstable_store.clone(),
+        )
+        .await;
+
+        // Corrupt the manifest's key range so it no longer matches the SST's real contents.
+        let key_range = info.key_range.as_mut().unwrap();
+        key_range.left = test_key_of(0).encode();
+        key_range.right = test_key_of(TEST_KEYS_COUNT / 2).encode();
+
+        let mut stats = StoreLocalStatistic::default();
+        let recomputed = recompute_key_range(&info, &sstable_store, &mut stats)
+            .await
+            .unwrap();
+
+        assert_eq!(recomputed.left, test_key_of(0).encode());
+        assert_eq!(recomputed.right, test_key_of(TEST_KEYS_COUNT - 1).encode());
+        assert!(!recomputed.right_exclusive);
+    }
+
+    #[tokio::test]
+    async fn test_validate_output_accepts_well_formed_tables() {
+        let sstable_store = mock_sstable_store();
+        let (_sst0, info0) = gen_test_sstable_and_info(
+            default_builder_opt_for_test(),
+            1 as HummockSstableObjectId,
+            (0..TEST_KEYS_COUNT / 2)
+                .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+            sstable_store.clone(),
+        )
+        .await;
+        let (_sst1, info1) = gen_test_sstable_and_info(
+            default_builder_opt_for_test(),
+            2 as HummockSstableObjectId,
+            (TEST_KEYS_COUNT / 2..TEST_KEYS_COUNT)
+                .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+            sstable_store.clone(),
+        )
+        .await;
+
+        let mut stats = StoreLocalStatistic::default();
+        validate_output(&[info0, info1], &sstable_store, &mut stats)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_validate_output_rejects_duplicate_key_across_tables() {
+        let sstable_store = mock_sstable_store();
+        let (_sst0, info0) = gen_test_sstable_and_info(
+            default_builder_opt_for_test(),
+            1 as HummockSstableObjectId,
+            (0..TEST_KEYS_COUNT / 2)
+                .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+            sstable_store.clone(),
+        )
+        .await;
+        // Malformed: the second output table re-emits the first table's last key instead of
+        // continuing strictly after it, as if a compaction split point duplicated a boundary key.
+        let (_sst1, info1) = gen_test_sstable_and_info(
+            default_builder_opt_for_test(),
+            2 as HummockSstableObjectId,
+            (TEST_KEYS_COUNT / 2 - 1..TEST_KEYS_COUNT)
+                .map(|i| (test_key_of(i), HummockValue::put(test_value_of(i)))),
+            sstable_store.clone(),
+        )
+        .await;
+
+        let mut stats = StoreLocalStatistic::default();
+        let err = validate_output(&[info0, info1], &sstable_store, &mut stats)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("duplicate"));
+    }
+}
@@ -157,6 +157,21 @@ impl HummockValue<Bytes> {
             HummockValue::Delete => HummockValue::Delete,
         }
     }
+
+    /// Decodes the object from an encoded [`Bytes`], slicing out the `Put` payload with a cheap
+    /// refcount bump instead of [`HummockValue::decode`]'s heap copy. Meant for callers that
+    /// already hold the encoded value as `Bytes` (e.g. straight out of a `Bytes`-backed block)
+    /// and want an owned [`HummockValue`] without paying for a copy.
+    pub fn from_encoded_bytes(mut buffer: Bytes) -> HummockResult<Self> {
+        if buffer.is_empty() {
+            return Err(HummockError::decode_error("empty value"));
+        }
+        match buffer.get_u8() {
+            VALUE_PUT => Ok(Self::Put(buffer)),
+            VALUE_DELETE => Ok(Self::Delete),
+            _ => Err(HummockError::decode_error("non-empty but format error")),
+        }
+    }
 }
 
 impl From<HummockValue<Vec<u8>>> for HummockValue<Bytes> {
@@ -200,4 +215,14 @@ mod tests {
             HummockValue::from_slice(&result).unwrap()
         );
     }
+
+    #[test]
+    fn test_bytes_decode_encode() {
+        let mut result = vec![];
+        HummockValue::Put(b"233333".to_vec()).encode(&mut result);
+        assert_eq!(
+            HummockValue::Put(Bytes::from_static(b"233333")),
+            HummockValue::from_encoded_bytes(Bytes::from(result)).unwrap()
+        );
+    }
 }
@@ -40,6 +40,8 @@ pub struct HummockStateStoreMetrics {
     pub iter_fetch_meta_duration: HistogramVec,
     pub iter_fetch_meta_cache_unhits: IntGauge,
     pub iter_slow_fetch_meta_cache_unhits: IntGauge,
+    pub iter_merge_seek_duration: HistogramVec,
+    pub iter_merge_seek_slow_child_counts: GenericCounterVec<AtomicU64>,
 
     pub read_req_bloom_filter_positive_counts: GenericCounterVec<AtomicU64>,
     pub read_req_positive_but_non_exist_counts: GenericCounterVec<AtomicU64>,
@@ -153,6 +155,22 @@ impl HummockStateStoreMetrics {
         )
         .unwrap();
 
+        let opts = histogram_opts!(
+            "state_store_iter_merge_seek_duration",
+            "Histogram of the slowest single child seek/rewind latency within a MergeIterator seek/rewind call",
+            exponential_buckets(0.0001, 2.0, 21).unwrap() // max 104s
+        );
+        let iter_merge_seek_duration =
+            register_histogram_vec_with_registry!(opts, &["table_id"], registry).unwrap();
+
+        let iter_merge_seek_slow_child_counts = register_int_counter_vec_with_registry!(
+            "state_store_iter_merge_seek_slow_child_counts",
+            "Total number of MergeIterator children whose seek/rewind latency exceeded the slow threshold",
+            &["table_id"],
+            registry
+        )
+        .unwrap();
+
         // ----- write_batch -----
         let write_batch_tuple_counts = register_int_counter_vec_with_registry!(
             "state_store_write_batch_tuple_counts",
@@ -271,6 +289,8 @@ impl HummockStateStoreMetrics {
             iter_fetch_meta_duration,
             iter_fetch_meta_cache_unhits,
             iter_slow_fetch_meta_cache_unhits,
+            iter_merge_seek_duration,
+            iter_merge_seek_slow_child_counts,
             read_req_bloom_filter_positive_counts,
             read_req_positive_but_non_exist_counts,
             read_req_check_bloom_filter_counts,
@@ -18,6 +18,12 @@ use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+
+/// A single child `seek`/`rewind` within a `MergeIterator` taking longer than this is considered
+/// slow and counted separately, so a few slow SSTs don't hide inside the aggregate latency of a
+/// wide merge.
+pub const MERGE_ITER_SLOW_CHILD_SEEK_THRESHOLD: Duration = Duration::from_millis(500);
 
 use prometheus::core::GenericLocalCounter;
 use prometheus::local::LocalHistogram;
@@ -28,6 +34,52 @@ use crate::monitor::CompactorMetrics;
 
 thread_local!(static LOCAL_METRICS: RefCell<HashMap<u32,LocalStoreMetrics>> = RefCell::new(HashMap::default()));
 
+/// Number of power-of-two buckets in a [`ValueSizeHistogram`]. Bucket `i` counts values whose
+/// encoded length falls in `[2^i, 2^(i+1))`, except the last bucket, which also catches anything
+/// at or above `2^(VALUE_SIZE_HISTOGRAM_BUCKETS - 1)`. 32 buckets covers value sizes up to 2 GiB,
+/// far beyond what a single value in this system is expected to reach.
+const VALUE_SIZE_HISTOGRAM_BUCKETS: usize = 32;
+
+/// A power-of-two bucketed histogram of value sizes, accumulated by
+/// [`SstableIterator`](crate::hummock::SstableIterator) when
+/// [`SstableIteratorReadOptions::collect_histogram`](crate::hummock::sstable::SstableIteratorReadOptions::collect_histogram)
+/// is set. This is plain in-process bucketing, not a Prometheus histogram: it's meant for a
+/// one-off look at a scan's value-size distribution, not for scraping.
+#[derive(Debug, Clone)]
+pub struct ValueSizeHistogram {
+    buckets: [u64; VALUE_SIZE_HISTOGRAM_BUCKETS],
+}
+
+impl Default for ValueSizeHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: [0; VALUE_SIZE_HISTOGRAM_BUCKETS],
+        }
+    }
+}
+
+impl ValueSizeHistogram {
+    pub fn record(&mut self, value_size: usize) {
+        let bucket = match value_size {
+            0 => 0,
+            n => (usize::BITS - 1 - n.leading_zeros()) as usize,
+        };
+        self.buckets[bucket.min(VALUE_SIZE_HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+
+    /// Returns the bucket counts in ascending order of value size, i.e. `buckets()[i]` is the
+    /// count for `[2^i, 2^(i+1))`.
+    pub fn buckets(&self) -> &[u64; VALUE_SIZE_HISTOGRAM_BUCKETS] {
+        &self.buckets
+    }
+
+    fn merge(&mut self, other: &ValueSizeHistogram) {
+        for (a, b) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            *a += b;
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct StoreLocalStatistic {
     pub cache_data_block_miss: u64,
@@ -43,6 +95,12 @@ pub struct StoreLocalStatistic {
     pub bloom_filter_true_negative_counts: u64,
     pub remote_io_time: Arc<AtomicU64>,
     pub bloom_filter_check_counts: u64,
+    /// Longest single-child `seek`/`rewind` latency, in nanoseconds, observed by any
+    /// `MergeIterator` that reported into this instance. Unlike the other counters, folding two
+    /// instances together takes the max rather than the sum, since the point is to surface the
+    /// single slowest child rather than an aggregate.
+    pub merge_iter_max_child_seek_duration_ns: u64,
+    pub merge_iter_slow_child_seek_count: u64,
     pub get_shared_buffer_hit_counts: u64,
     pub staging_imm_iter_count: u64,
     pub staging_sst_iter_count: u64,
@@ -57,6 +115,12 @@ pub struct StoreLocalStatistic {
     pub overlapping_get_count: u64,
     pub non_overlapping_get_count: u64,
 
+    /// Distribution of decoded value sizes observed by a `SstableIterator` created with
+    /// `SstableIteratorReadOptions::collect_histogram` set. `None` when histogram collection is
+    /// off, which is the default, so scans that don't ask for it pay nothing beyond this `Option`
+    /// check.
+    pub value_size_histogram: Option<Box<ValueSizeHistogram>>,
+
     #[cfg(all(debug_assertions, not(any(madsim, test, feature = "test"))))]
     reported: AtomicBool,
     #[cfg(all(debug_assertions, not(any(madsim, test, feature = "test"))))]
@@ -73,6 +137,15 @@ impl StoreLocalStatistic {
             Ordering::Relaxed,
         );
         self.bloom_filter_check_counts += other.bloom_filter_check_counts;
+        self.merge_iter_max_child_seek_duration_ns = self
+            .merge_iter_max_child_seek_duration_ns
+            .max(other.merge_iter_max_child_seek_duration_ns);
+        self.merge_iter_slow_child_seek_count += other.merge_iter_slow_child_seek_count;
+        if let Some(other_histogram) = other.value_size_histogram.as_ref() {
+            self.value_size_histogram
+                .get_or_insert_with(Default::default)
+                .merge(other_histogram);
+        }
 
         #[cfg(all(debug_assertions, not(any(madsim, test, feature = "test"))))]
         if other.added.fetch_or(true, Ordering::Relaxed) || other.reported.load(Ordering::Relaxed) {
@@ -92,6 +165,16 @@ impl StoreLocalStatistic {
         if t > 0.0 {
             metrics.remote_io_time.observe(t / 1000.0);
         }
+        if self.merge_iter_max_child_seek_duration_ns > 0 {
+            metrics
+                .merge_iter_seek_duration
+                .observe(self.merge_iter_max_child_seek_duration_ns as f64 / 1_000_000_000.0);
+        }
+        if self.merge_iter_slow_child_seek_count > 0 {
+            metrics
+                .merge_iter_slow_child_seek_count
+                .inc_by(self.merge_iter_slow_child_seek_count);
+        }
 
         metrics.collect_count += 1;
         if metrics.collect_count > FLUSH_LOCAL_METRICS_TIMES {
@@ -197,6 +280,8 @@ impl StoreLocalStatistic {
             || self.bloom_filter_true_negative_counts != 0
             || self.remote_io_time.load(Ordering::Relaxed) != 0
             || self.bloom_filter_check_counts != 0
+            || self.merge_iter_max_child_seek_duration_ns != 0
+            || self.merge_iter_slow_child_seek_count != 0
     }
 }
 
@@ -212,12 +297,43 @@ impl Drop for StoreLocalStatistic {
     }
 }
 
+/// Aggregates [`StoreLocalStatistic`] per child, keyed by a caller-chosen label (e.g. a level
+/// name), so detailed read profiling can tell which child contributed what. Plain
+/// [`StoreLocalStatistic::add`] loses this attribution by summing everything into one instance; a
+/// merge iterator wanting per-child detail should call [`Self::add_labeled`] once per child
+/// instead.
+#[derive(Default, Debug)]
+pub struct LabeledStoreLocalStatistic {
+    by_label: HashMap<String, StoreLocalStatistic>,
+}
+
+impl LabeledStoreLocalStatistic {
+    pub fn add_labeled(&mut self, label: impl Into<String>, stats: &StoreLocalStatistic) {
+        self.by_label.entry(label.into()).or_default().add(stats);
+    }
+
+    pub fn get(&self, label: &str) -> Option<&StoreLocalStatistic> {
+        self.by_label.get(label)
+    }
+
+    /// Sums every label's stats into a single [`StoreLocalStatistic`], discarding attribution.
+    pub fn collapse(&self) -> StoreLocalStatistic {
+        let mut total = StoreLocalStatistic::default();
+        for stats in self.by_label.values() {
+            total.add(stats);
+        }
+        total
+    }
+}
+
 struct LocalStoreMetrics {
     cache_data_block_total: GenericLocalCounter<prometheus::core::AtomicU64>,
     cache_data_block_miss: GenericLocalCounter<prometheus::core::AtomicU64>,
     cache_meta_block_total: GenericLocalCounter<prometheus::core::AtomicU64>,
     cache_meta_block_miss: GenericLocalCounter<prometheus::core::AtomicU64>,
     remote_io_time: LocalHistogram,
+    merge_iter_seek_duration: LocalHistogram,
+    merge_iter_slow_child_seek_count: GenericLocalCounter<prometheus::core::AtomicU64>,
     processed_key_count: GenericLocalCounter<prometheus::core::AtomicU64>,
     skip_multi_version_key_count: GenericLocalCounter<prometheus::core::AtomicU64>,
     skip_delete_key_count: GenericLocalCounter<prometheus::core::AtomicU64>,
@@ -269,6 +385,16 @@ impl LocalStoreMetrics {
             .with_label_values(&[table_id_label])
             .local();
 
+        let merge_iter_seek_duration = metrics
+            .iter_merge_seek_duration
+            .with_label_values(&[table_id_label])
+            .local();
+
+        let merge_iter_slow_child_seek_count = metrics
+            .iter_merge_seek_slow_child_counts
+            .with_label_values(&[table_id_label])
+            .local();
+
         let processed_key_count = metrics
             .iter_scan_key_counts
             .with_label_values(&[table_id_label, "processed"])
@@ -346,6 +472,8 @@ impl LocalStoreMetrics {
             cache_meta_block_total,
             cache_meta_block_miss,
             remote_io_time,
+            merge_iter_seek_duration,
+            merge_iter_slow_child_seek_count,
             processed_key_count,
             skip_multi_version_key_count,
             skip_delete_key_count,
@@ -370,6 +498,8 @@ impl LocalStoreMetrics {
 
     pub fn flush(&mut self) {
         self.remote_io_time.flush();
+        self.merge_iter_seek_duration.flush();
+        self.merge_iter_slow_child_seek_count.flush();
         self.iter_filter_metrics.flush();
         self.get_filter_metrics.flush();
         self.flush_histogram();
@@ -590,3 +720,34 @@ impl Drop for MayExistLocalMetricsGuard {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labeled_store_local_statistic() {
+        let mut labeled = LabeledStoreLocalStatistic::default();
+
+        let mut level0 = StoreLocalStatistic::default();
+        level0.cache_data_block_total = 10;
+        level0.cache_data_block_miss = 2;
+        labeled.add_labeled("level-0", &level0);
+        level0.ignore();
+
+        let mut level1 = StoreLocalStatistic::default();
+        level1.cache_data_block_total = 5;
+        level1.cache_data_block_miss = 1;
+        labeled.add_labeled("level-1", &level1);
+        level1.ignore();
+
+        assert_eq!(labeled.get("level-0").unwrap().cache_data_block_total, 10);
+        assert_eq!(labeled.get("level-1").unwrap().cache_data_block_total, 5);
+        assert!(labeled.get("level-2").is_none());
+
+        let collapsed = labeled.collapse();
+        assert_eq!(collapsed.cache_data_block_total, 15);
+        assert_eq!(collapsed.cache_data_block_miss, 3);
+        collapsed.ignore();
+    }
+}
@@ -51,6 +51,7 @@ impl<'a, R: Rng + 'a> SqlGenerator<'a, R> {
             table_name,
             columns: vec![],
             source: Box::new(source),
+            on_conflict: None,
             returning: vec![],
         }
     }
@@ -393,3 +393,23 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_dml_includes_returning_variants() {
+        for stmt_type in [
+            StatementType::INSERT,
+            StatementType::DELETE,
+            StatementType::UPDATE,
+            StatementType::INSERT_RETURNING,
+            StatementType::DELETE_RETURNING,
+            StatementType::UPDATE_RETURNING,
+        ] {
+            assert!(stmt_type.is_dml(), "{:?} should be considered DML", stmt_type);
+        }
+        assert!(!StatementType::SELECT.is_dml());
+    }
+}